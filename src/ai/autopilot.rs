@@ -0,0 +1,377 @@
+use std::collections::VecDeque;
+use crate::controller::{self, Controller};
+use crate::game::board::Board;
+use crate::game::config::{BoundaryRule, GameConfig};
+use crate::game::game_state::{Direction, Position};
+use crate::game::hazards::HazardPhase;
+use crate::game::simulation::Simulation;
+
+// งูที่ยาวไม่เกินนี้กลับตัวได้ในห้อง 2x2 เสมอ ไม่ต้องหาทางกลับไปหาหาง
+const SHORT_SNAKE: usize = 3;
+
+// AI เล่นเกมเอง: หาทางสั้นสุดไปหาอาหารด้วย BFS
+// ถ้ากินแล้วยังเดินกลับไปหาหางได้ถึงจะไป ไม่งั้นเดินตามหางไปก่อน
+// ถ้าหาหางไม่เจอด้วย เลือกทางที่เหลือที่ว่างให้เดินมากที่สุด
+pub struct Autopilot {
+    // ไม่ให้ไปช่องที่ไม่มีทางกลับไปหาหาง (ปิดได้เพื่อเทียบความเก่ง)
+    pub check_safety: bool,
+    // จำนวนการขยับตั้งแต่กินอาหารครั้งล่าสุด ใช้รู้ว่าวนตามหางนานเกินไปแล้ว
+    moves_since_food: usize,
+    food_eaten: u32,
+}
+
+impl Default for Autopilot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// สภาพกระดานที่ AI มองเห็น ณ การขยับครั้งถัดไป
+#[derive(Clone)]
+struct View {
+    board: Board,
+    boundary: BoundaryRule,
+    // ช่องที่เดินไม่ได้ตลอด (obstacles, hazard ที่ไม่เปิด)
+    walls: Vec<bool>,
+    // ตัวงู หัวอยู่ index 0
+    body: Vec<Position>,
+    // งูหันกลับทางเดิมไม่ได้
+    direction: Direction,
+}
+
+impl View {
+    fn from_simulation(sim: &Simulation) -> Self {
+        let board = sim.config.board;
+        let mut walls = vec![false; board.cell_count()];
+        for obstacle in &sim.obstacles {
+            walls[board.index(obstacle)] = true;
+        }
+        for hazard in &sim.hazards {
+            // hazard ที่กำลังเตือนจะตันในไม่ช้า ถือว่าเดินไม่ได้ไปด้วย
+            if hazard.phase != HazardPhase::Open {
+                for cell in hazard.cells() {
+                    if board.contains(&cell) {
+                        walls[board.index(&cell)] = true;
+                    }
+                }
+            }
+        }
+        View {
+            board,
+            boundary: sim.config.boundary,
+            walls,
+            body: sim.snake.body.iter().copied().collect(),
+            direction: sim.snake.direction,
+        }
+    }
+
+    // แต่ละช่องต้องขยับอย่างน้อยกี่ก้าวถึงจะเดินเข้าไปได้ (0 = ว่างอยู่แล้ว)
+    // ตรวจชนตัวเองก่อนหางหด ปล้อง body[i] จึงหลบให้ตอนก้าวที่ len - i + 1
+    fn free_after(&self) -> Vec<usize> {
+        let mut free_after = vec![0; self.board.cell_count()];
+        let len = self.body.len();
+        for (i, segment) in self.body.iter().enumerate() {
+            let index = self.board.index(segment);
+            free_after[index] = free_after[index].max(len - i + 1);
+        }
+        free_after
+    }
+
+    // BFS จากหัวงู ช่องตัวงูเดินผ่านได้เมื่อปล้องนั้นหลุดไปแล้วตอนไปถึง
+    // คืนค่า parent ของแต่ละช่องสำหรับย้อนเส้นทาง
+    fn search(&self) -> Vec<Option<Position>> {
+        let start = self.body[0];
+        let cells = self.board.cell_count();
+        let free_after = self.free_after();
+        let mut parent = vec![None; cells];
+        let mut distance = vec![usize::MAX; cells];
+        let mut queue = VecDeque::new();
+        distance[self.board.index(&start)] = 0;
+        queue.push_back(start);
+        while let Some(position) = queue.pop_front() {
+            let steps = distance[self.board.index(&position)] + 1;
+            for direction in Direction::ALL {
+                if position == start && direction == self.direction.opposite() {
+                    continue;
+                }
+                if let Some(next) = self.board.neighbor(position, direction, self.boundary) {
+                    let index = self.board.index(&next);
+                    // ช่องที่ยังไม่ว่างตอนไปถึง อาจไปถึงได้ทีหลังจากทางอื่นที่อ้อมกว่า
+                    if self.walls[index] || distance[index] != usize::MAX || steps < free_after[index] {
+                        continue;
+                    }
+                    distance[index] = steps;
+                    parent[index] = Some(position);
+                    queue.push_back(next);
+                }
+            }
+        }
+        parent
+    }
+
+    fn path_to(&self, target: Position) -> Option<Vec<Position>> {
+        let head = self.body[0];
+        let parent = self.search();
+        let mut path = Vec::new();
+        let mut current = target;
+        while current != head {
+            path.push(current);
+            current = parent[self.board.index(&current)]?;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    // เดินตามเส้นทางแล้วดูตัวงูที่ได้ (กินอาหารตอนก้าวสุดท้าย)
+    fn follow(&self, path: &[Position]) -> View {
+        let mut body: VecDeque<Position> = self.body.iter().copied().collect();
+        for (i, step) in path.iter().enumerate() {
+            body.push_front(*step);
+            if i + 1 < path.len() {
+                body.pop_back();
+            }
+        }
+        let direction = match (body.front(), body.get(1)) {
+            (Some(&head), Some(&neck)) => self.direction_to(neck, head).unwrap_or(self.direction),
+            _ => self.direction,
+        };
+        View {
+            board: self.board,
+            boundary: self.boundary,
+            walls: self.walls.clone(),
+            body: body.into_iter().collect(),
+            direction,
+        }
+    }
+
+    fn can_reach_tail(&self) -> bool {
+        match self.body.last() {
+            Some(&tail) if self.body.len() > 1 => self.path_to(tail).is_some(),
+            _ => true,
+        }
+    }
+
+    // ทางไปอาหารที่เข้าทางช่องข้างเคียงแต่ละด้าน (ไม่ผ่านช่องอาหารก่อน) เลือกทางสั้นสุดที่ปลอดภัย
+    fn safe_path_around(&self, food: Position) -> Option<Vec<Position>> {
+        let mut detour = self.clone();
+        detour.walls[self.board.index(&food)] = true;
+        Direction::ALL
+            .iter()
+            .filter_map(|direction| self.board.neighbor(food, *direction, self.boundary))
+            .filter_map(|approach| {
+                let mut path = if approach == self.body[0] { Vec::new() } else { detour.path_to(approach)? };
+                path.push(food);
+                Some(path)
+            })
+            .filter(|path| self.follow(path).is_safe())
+            .min_by_key(|path| path.len())
+    }
+
+    // จำนวนช่องว่างที่หัวงูยังเดินไปได้ โดยปล้องที่ยังไม่หลุดนับเป็นกำแพง
+    fn room(&self) -> usize {
+        let mut blocked = self.walls.clone();
+        for segment in self.body.iter().skip(1).take(self.body.len().saturating_sub(2)) {
+            blocked[self.board.index(segment)] = true;
+        }
+        self.board
+            .flood_fill(self.body[0], self.boundary, &blocked)
+            .iter()
+            .filter(|reached| **reached)
+            .count()
+            .saturating_sub(1)
+    }
+
+    // หาหางเจอ หรืองูยังสั้นและมีที่ว่างพอให้กลับตัว
+    // BFS เดินผ่านช่องซ้ำไม่ได้ ทางกลับหาหางที่ต้องวนในห้องแคบจึงหาไม่เจอ (เช่นอาหารอยู่บนทางแคบช่องเดียว)
+    fn is_safe(&self) -> bool {
+        self.can_reach_tail() || (self.body.len() <= SHORT_SNAKE && self.room() > SHORT_SNAKE * 2)
+    }
+
+    // ทิศที่ก้าวจาก from ไปช่องข้างเคียง to (รวมการทะลุขอบ)
+    fn direction_to(&self, from: Position, to: Position) -> Option<Direction> {
+        Direction::ALL
+            .into_iter()
+            .find(|direction| self.board.neighbor(from, *direction, self.boundary) == Some(to))
+    }
+
+    // จำนวนช่องที่ไปถึงได้ถ้าก้าวไป next
+    fn space_after(&self, next: Position) -> usize {
+        let mut blocked = self.walls.clone();
+        // ปล้องสุดท้ายจะหลุดไปตอนก้าวนี้ แต่ช่องหัวเดิมเดินกลับไม่ได้เสมอ (ห้ามกลับหลัง)
+        for segment in &self.body[..self.body.len() - 1] {
+            blocked[self.board.index(segment)] = true;
+        }
+        blocked[self.board.index(&self.body[0])] = true;
+        self.board
+            .flood_fill(next, self.boundary, &blocked)
+            .iter()
+            .filter(|reached| **reached)
+            .count()
+    }
+}
+
+// ก้าวไปทางนี้ตอนนี้แล้วตายทันทีหรือไม่ ขอบแบบ bounce ไม่ตายจึงนับว่าไปได้
+fn is_fatal(sim: &Simulation, direction: Direction) -> bool {
+    let head = match sim.snake.body.front() {
+        Some(head) => *head,
+        None => return true,
+    };
+    match sim.config.board.neighbor(head, direction, sim.config.boundary) {
+        Some(next) => {
+            !sim.power_up_manager.ghost_mode && (sim.occupancy.has_snake(&next) || sim.occupancy.has_obstacle(&next))
+        }
+        None => sim.config.boundary != BoundaryRule::Bounce,
+    }
+}
+
+impl Autopilot {
+    pub fn new() -> Self {
+        Autopilot {
+            check_safety: true,
+            moves_since_food: 0,
+            food_eaten: 0,
+        }
+    }
+
+    // ไปหาอาหารทางสั้นสุดเสมอโดยไม่ตรวจทางกลับ ใช้เทียบความเก่ง
+    pub fn greedy() -> Self {
+        Autopilot {
+            check_safety: false,
+            ..Self::new()
+        }
+    }
+
+    // ทิศที่งูควรไปในการขยับครั้งถัดไป (None = ไปทางเดิม)
+    pub fn choose_direction(&mut self, sim: &Simulation) -> Option<Direction> {
+        let view = View::from_simulation(sim);
+        let head = view.body[0];
+        let food = sim.food.position;
+        let free_after = view.free_after();
+
+        if sim.food_eaten != self.food_eaten {
+            self.food_eaten = sim.food_eaten;
+            self.moves_since_food = 0;
+        }
+        self.moves_since_food += 1;
+        let stalled = self.moves_since_food > view.board.cell_count() / 2;
+
+        if let Some(path) = view.path_to(food) {
+            let after = view.follow(&path);
+            if !self.check_safety || after.is_safe() {
+                return view.direction_to(head, path[0]);
+            }
+            // ทางสั้นสุดเข้าไปติดในซอก ลองเข้าหาอาหารจากช่องข้าง ๆ ด้านอื่นแทน
+            if let Some(path) = view.safe_path_around(food) {
+                return view.direction_to(head, path[0]);
+            }
+            // BFS หาทางกลับไปหาหางที่ต้องรอให้ตัวงูพ้นทางไม่เจอ (เช่นอาหารท้ายทางแคบในเขาวงกต)
+            // วนตามหางนานเกินแล้วก็ยอมไป ถ้าหลังกินยังมีที่ว่างพอให้ทั้งตัวงูวนรอได้
+            if stalled && after.room() >= after.body.len() {
+                return view.direction_to(head, path[0]);
+            }
+        }
+
+        // เดินตามหางโดยเลือกก้าวที่ยังกลับไปหาหางได้และอยู่ไกลอาหารที่สุด
+        // ถ่วงเวลาให้ตัวงูคลายออกก่อน และไม่วนซ้ำทางเดิมตลอดไป
+        if view.body.len() > 1 {
+            let mut safe_steps: Vec<(Direction, Position)> = Direction::ALL
+                .into_iter()
+                .filter(|direction| *direction != view.direction.opposite())
+                .filter_map(|direction| {
+                    let next = view.board.neighbor(head, direction, view.boundary)?;
+                    let index = view.board.index(&next);
+                    let free = !view.walls[index] && free_after[index] <= 1;
+                    (free && next != food && view.follow(&[next]).can_reach_tail()).then_some((direction, next))
+                })
+                .collect();
+            safe_steps.sort_by_key(|(_, next)| std::cmp::Reverse(view.board.distance(*next, food, view.boundary)));
+            // วนนานเกินแล้วสลับก้าวไปมาแบบกำหนดได้ ให้ตัวงูเรียงใหม่จนมีทางเข้าหาอาหารที่ปลอดภัย
+            let pick = if stalled { (self.moves_since_food.wrapping_mul(2654435761) >> 7) % safe_steps.len().max(1) } else { 0 };
+            if let Some((direction, _)) = safe_steps.get(pick) {
+                return Some(*direction);
+            }
+        }
+
+        // ไม่มีทางที่ปลอดภัย เลือกช่องข้างเคียงที่ยังมีที่ว่างเหลือมากที่สุด
+        let roomiest = Direction::ALL
+            .iter()
+            .filter(|direction| **direction != view.direction.opposite())
+            .filter_map(|direction| {
+                let next = view.board.neighbor(head, *direction, view.boundary)?;
+                let index = view.board.index(&next);
+                (!view.walls[index] && free_after[index] <= 1).then(|| (*direction, view.space_after(next)))
+            })
+            .max_by_key(|(_, space)| *space)
+            .map(|(direction, _)| direction);
+        // ทุกทางดูอันตราย (เช่น hazard กำลังเตือน) ก็ยังดีกว่าวิ่งชนสิ่งที่ตายแน่
+        roomiest.or_else(|| {
+            Direction::ALL
+                .into_iter()
+                .filter(|direction| *direction != view.direction.opposite())
+                .find(|direction| !is_fatal(sim, *direction))
+        })
+    }
+}
+
+impl Controller for Autopilot {
+    fn reset(&mut self) {
+        self.moves_since_food = 0;
+        self.food_eaten = 0;
+    }
+
+    fn next_direction(&mut self, sim: &Simulation) -> Option<Direction> {
+        controller::plan_on_move(sim, |sim| self.choose_direction(sim))
+    }
+
+    fn name(&self) -> &'static str {
+        "AI"
+    }
+}
+
+// เล่นหนึ่งเกมจนจบหรือครบ max_ticks โดยไม่ต้องเปิดหน้าต่าง ใช้ทดสอบความยากของด่าน
+pub fn run_autopilot(config: GameConfig, seed: u64, max_ticks: u64) -> Simulation {
+    controller::play(&mut Autopilot::new(), config, seed, max_ticks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::game_state::GameMode;
+
+    // เล่นยาว ๆ ในเขาวงกตหลายแบบ งูต้องได้กินอาหารเรื่อย ๆ ไม่วนตามหางค้างอยู่ที่เดิม
+    #[test]
+    fn keeps_eating_in_mazes() {
+        const TARGET_SCORE: u32 = 60;
+        const MAX_TICKS_WITHOUT_FOOD: u64 = 10_000;
+        let cases = [
+            (Board::new(20, 15), BoundaryRule::Solid, [0, 1, 4, 7]),
+            (Board::new(40, 30), BoundaryRule::Solid, [2, 5, 9, 0]),
+            (Board::new(40, 30), BoundaryRule::Wrap, [2, 4, 5, 6]),
+        ];
+        for (board, boundary, seeds) in cases {
+            let config = GameConfig::new(GameMode::Maze, board, boundary);
+            for seed in seeds {
+                let mut autopilot = Autopilot::new();
+                let mut sim = Simulation::new(config.clone(), seed);
+                let mut last_meal = 0;
+                while !sim.is_over() && sim.food_eaten < TARGET_SCORE {
+                    let eaten = sim.food_eaten;
+                    controller::step(&mut autopilot, &mut sim);
+                    if sim.food_eaten != eaten {
+                        last_meal = sim.tick;
+                    }
+                    assert!(
+                        sim.tick - last_meal < MAX_TICKS_WITHOUT_FOOD,
+                        "{}x{} {:?} seed {} stuck at {} food",
+                        board.width,
+                        board.height,
+                        boundary,
+                        seed,
+                        sim.food_eaten
+                    );
+                }
+                assert_eq!(sim.death_cause, None, "{}x{} {:?} seed {} died", board.width, board.height, boundary, seed);
+            }
+        }
+    }
+}
@@ -0,0 +1,446 @@
+use crate::game::config::{BoundaryRule, GameConfig};
+use crate::game::game_state::{Direction, Position, PowerUpType};
+use crate::game::simulation::{DeathCause, GameEvent, Simulation};
+
+// ช่องของ grid observation เรียงตามนี้: ตัวงู, หัวงู, อาหาร, สิ่งกีดขวาง, Power-up
+pub const GRID_CHANNELS: usize = 5;
+// feature vector เรียงตามนี้:
+// อันตรายข้างหน้า/ซ้าย/ขวา (3), ทิศปัจจุบัน บน/ล่าง/ซ้าย/ขวา (4),
+// ระยะถึงอาหารแกน x/y หารด้วยขนาดกระดาน (2), ความยาวงูต่อจำนวนช่อง (1),
+// มี Power-up บนกระดาน (1), กำลังเร่งความเร็ว (1), อยู่ในโหมดทะลุ (1)
+pub const FEATURE_COUNT: usize = 13;
+
+// การกระทำเทียบกับทิศที่งูหันอยู่ งูหันกลับหลังไม่ได้จึงมีแค่สามทาง
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Action {
+    Straight,
+    TurnLeft,
+    TurnRight,
+}
+
+impl Action {
+    pub const ALL: [Action; 3] = [Action::Straight, Action::TurnLeft, Action::TurnRight];
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        Action::ALL.get(index).copied()
+    }
+
+    pub fn index(self) -> usize {
+        match self {
+            Action::Straight => 0,
+            Action::TurnLeft => 1,
+            Action::TurnRight => 2,
+        }
+    }
+
+    pub fn apply(self, direction: Direction) -> Direction {
+        match self {
+            Action::Straight => direction,
+            Action::TurnLeft => turn_left(direction),
+            Action::TurnRight => turn_left(direction).opposite(),
+        }
+    }
+}
+
+fn turn_left(direction: Direction) -> Direction {
+    match direction {
+        Direction::Up => Direction::Left,
+        Direction::Left => Direction::Down,
+        Direction::Down => Direction::Right,
+        Direction::Right => Direction::Up,
+    }
+}
+
+// รางวัลของแต่ละเหตุการณ์ ปรับได้ตามที่ต้องการฝึก
+#[derive(Clone, PartialEq, Debug)]
+pub struct RewardConfig {
+    pub food: f32,
+    pub power_up: f32,
+    pub death: f32,
+    pub won: f32,
+    pub level_complete: f32,
+    // ได้ทุกครั้งที่งูขยับ ใส่ค่าลบเพื่อเร่งให้หาอาหารเร็ว
+    pub step: f32,
+    // ได้ตอนจบเพราะเดินวนนานเกินไม่ได้กินอาหาร
+    pub truncated: f32,
+}
+
+impl Default for RewardConfig {
+    fn default() -> Self {
+        RewardConfig {
+            food: 1.0,
+            power_up: 0.0,
+            death: -1.0,
+            won: 10.0,
+            level_complete: 5.0,
+            step: 0.0,
+            truncated: 0.0,
+        }
+    }
+}
+
+// ข้อมูลให้ agent ทั้งสองแบบ grid เป็น [channel][y][x] เรียงต่อกันเป็นแถวเดียว
+#[derive(Clone, PartialEq, Debug)]
+pub struct Observation {
+    pub grid: Vec<f32>,
+    pub features: Vec<f32>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct StepInfo {
+    // เหตุการณ์ทุกอย่างใน tick ที่เดินไประหว่างการขยับครั้งนี้
+    pub events: Vec<GameEvent>,
+    pub score: usize,
+    pub food_eaten: u32,
+    pub ticks: u64,
+    pub death_cause: Option<DeathCause>,
+    // จบเพราะครบ max_moves_without_food ไม่ใช่เพราะเกมจบ
+    pub truncated: bool,
+}
+
+// เกมในรูปแบบ gym: reset แล้ว step ทีละการขยับหนึ่งช่อง ใช้กติกาเดียวกับตัวเกมทุกอย่าง
+pub struct SnakeEnv {
+    pub config: GameConfig,
+    pub rewards: RewardConfig,
+    // จบ episode ถ้างูขยับเกินนี้โดยไม่ได้กินอาหาร (0 = ไม่จำกัด)
+    pub max_moves_without_food: u32,
+    sim: Simulation,
+    moves_without_food: u32,
+    truncated: bool,
+}
+
+impl SnakeEnv {
+    pub fn new(config: GameConfig, rewards: RewardConfig) -> Self {
+        let max_moves_without_food = config.board.cell_count() as u32 * 2;
+        SnakeEnv {
+            sim: Simulation::new(config.clone(), 0),
+            config,
+            rewards,
+            max_moves_without_food,
+            moves_without_food: 0,
+            truncated: false,
+        }
+    }
+
+    pub fn simulation(&self) -> &Simulation {
+        &self.sim
+    }
+
+    // ขนาดของ grid observation เป็น (channel, height, width)
+    pub fn grid_shape(&self) -> (usize, usize, usize) {
+        let board = self.config.board;
+        (GRID_CHANNELS, board.height as usize, board.width as usize)
+    }
+
+    pub fn reset(&mut self, seed: u64) -> Observation {
+        self.sim = Simulation::new(self.config.clone(), seed);
+        self.moves_without_food = 0;
+        self.truncated = false;
+        self.observe()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.sim.is_over() || self.truncated
+    }
+
+    // เดินจนงูขยับหนึ่งช่องหรือเกมจบ เรียกหลังจบแล้วได้รางวัล 0
+    pub fn step(&mut self, action: Action) -> (Observation, f32, bool, StepInfo) {
+        let mut events = Vec::new();
+        if !self.is_done() {
+            while !self.sim.is_over() && !self.sim.moves_next_step() {
+                events.extend(self.sim.step(None));
+            }
+            if !self.sim.is_over() {
+                let direction = action.apply(self.sim.snake.direction);
+                events.extend(self.sim.step(Some(direction)));
+            }
+        }
+
+        let mut reward = 0.0;
+        for event in &events {
+            reward += match event {
+                GameEvent::Moved => self.rewards.step,
+                GameEvent::AteFood => self.rewards.food,
+                GameEvent::PowerUpCollected(_) => self.rewards.power_up,
+                GameEvent::Died(_) => self.rewards.death,
+                GameEvent::BoardFilled => self.rewards.won,
+                GameEvent::LevelComplete => self.rewards.level_complete,
+                _ => 0.0,
+            };
+        }
+
+        if events.iter().any(|event| matches!(event, GameEvent::AteFood)) {
+            self.moves_without_food = 0;
+        } else if events.iter().any(|event| matches!(event, GameEvent::Moved)) {
+            self.moves_without_food += 1;
+            if !self.sim.is_over()
+                && self.max_moves_without_food > 0
+                && self.moves_without_food >= self.max_moves_without_food
+            {
+                self.truncated = true;
+                reward += self.rewards.truncated;
+            }
+        }
+
+        let info = StepInfo {
+            events,
+            score: self.sim.score(),
+            food_eaten: self.sim.food_eaten,
+            ticks: self.sim.tick,
+            death_cause: self.sim.death_cause,
+            truncated: self.truncated,
+        };
+        (self.observe(), reward, self.is_done(), info)
+    }
+
+    pub fn observe(&self) -> Observation {
+        Observation {
+            grid: self.grid(),
+            features: self.features(),
+        }
+    }
+
+    fn grid(&self) -> Vec<f32> {
+        let board = self.config.board;
+        let plane = board.cell_count();
+        let mut grid = vec![0.0; GRID_CHANNELS * plane];
+        let mut set = |channel: usize, position: &Position| {
+            if board.contains(position) {
+                grid[channel * plane + board.index(position)] = 1.0;
+            }
+        };
+        for segment in &self.sim.snake.body {
+            set(0, segment);
+        }
+        if let Some(head) = self.sim.snake.body.front() {
+            set(1, head);
+        }
+        set(2, &self.sim.food.position);
+        for y in 0..board.height {
+            for x in 0..board.width {
+                let position = Position { x, y };
+                if self.sim.occupancy.has_obstacle(&position) {
+                    set(3, &position);
+                }
+            }
+        }
+        for power_up in &self.sim.power_up_manager.power_ups {
+            set(4, &power_up.position);
+        }
+        grid
+    }
+
+    // ก้าวไปทางนี้แล้วตายทันทีหรือไม่ ตรวจชนตัวเองก่อนหางหด ปลายหางจึงนับเป็นอันตรายด้วย
+    fn is_danger(&self, direction: Direction) -> bool {
+        let sim = &self.sim;
+        let head = match sim.snake.body.front() {
+            Some(head) => *head,
+            None => return true,
+        };
+        let next = match (sim.config.board.neighbor(head, direction, sim.config.boundary), sim.config.boundary) {
+            (Some(next), _) => next,
+            (None, BoundaryRule::Bounce) => match self.bounce_head(direction) {
+                Some(next) => next,
+                None => return true,
+            },
+            (None, _) => return true,
+        };
+        // โหมดทะลุผ่านได้ทั้งตัวเองและสิ่งกีดขวาง
+        !self.ghost_until_next_move() && (sim.occupancy.has_snake(&next) || sim.occupancy.has_obstacle(&next))
+    }
+
+    // ชนขอบแบบ bounce งูกลับหัวเป็นหาง แล้วเดินต่อจากปลายหางตามทิศที่หางชี้ออกไป
+    fn bounce_head(&self, direction: Direction) -> Option<Position> {
+        let body = &self.sim.snake.body;
+        let tail = *body.back()?;
+        let reversed = direction.opposite();
+        let direction = match body.len() {
+            1 => reversed,
+            len => Direction::between(body[len - 2], tail).unwrap_or(reversed),
+        };
+        Some(direction.offset(tail)).filter(|next| self.config.board.contains(next))
+    }
+
+    // Ghost อาจหมดเวลาใน tick ที่รองูขยับ นับว่าทะลุได้เฉพาะเมื่อยังอยู่ถึงตอนขยับ
+    // เก็บ Ghost ซ้อนกันได้ แต่ตัวแรกที่หมดเวลาจะปิดโหมดทะลุทันที
+    fn ghost_until_next_move(&self) -> bool {
+        let sim = &self.sim;
+        let ticks_to_move = sim.ticks_per_move().saturating_sub(sim.ticks_since_move).max(1);
+        sim.power_up_manager.ghost_mode
+            && sim
+                .power_up_manager
+                .active_power_ups
+                .iter()
+                .filter(|(power_type, _)| *power_type == PowerUpType::GhostMode)
+                .all(|(_, duration)| *duration > ticks_to_move)
+    }
+
+    // ระยะตามแกนจาก from ไป to ถ้าทะลุขอบได้ใช้ทางที่สั้นกว่า
+    fn axis_delta(&self, from: i32, to: i32, size: i32) -> f32 {
+        let mut delta = to - from;
+        if self.config.boundary == BoundaryRule::Wrap {
+            if delta > size / 2 {
+                delta -= size;
+            } else if delta < -size / 2 {
+                delta += size;
+            }
+        }
+        delta as f32 / size as f32
+    }
+
+    fn features(&self) -> Vec<f32> {
+        let sim = &self.sim;
+        let board = self.config.board;
+        let direction = sim.snake.direction;
+        let flag = |value: bool| if value { 1.0 } else { 0.0 };
+        let mut features = Vec::with_capacity(FEATURE_COUNT);
+
+        for action in Action::ALL {
+            features.push(flag(self.is_danger(action.apply(direction))));
+        }
+        for each in Direction::ALL {
+            features.push(flag(direction == each));
+        }
+        let head = sim.snake.body.front().copied().unwrap_or(sim.food.position);
+        features.push(self.axis_delta(head.x, sim.food.position.x, board.width));
+        features.push(self.axis_delta(head.y, sim.food.position.y, board.height));
+        features.push(sim.snake.len() as f32 / board.cell_count() as f32);
+        features.push(flag(!sim.power_up_manager.power_ups.is_empty()));
+        features.push(flag(sim.power_up_manager.speed_multiplier > 1.0));
+        features.push(flag(sim.power_up_manager.ghost_mode));
+        features
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::board::Board;
+    use crate::game::game_state::GameMode;
+    use crate::game::occupancy::Layer;
+
+    fn env(board: Board, boundary: BoundaryRule) -> SnakeEnv {
+        let mut config = GameConfig::new(GameMode::Normal, board, boundary);
+        config.power_up_chance = Some(0.0);
+        let rewards = RewardConfig { food: 2.0, death: -3.0, step: -0.01, truncated: -0.5, ..RewardConfig::default() };
+        SnakeEnv::new(config, rewards)
+    }
+
+    fn place_food(env: &mut SnakeEnv, position: Position) {
+        let sim = &mut env.sim;
+        sim.occupancy.remove(&sim.food.position, Layer::Item);
+        sim.food.position = position;
+        sim.occupancy.add(&position, Layer::Item);
+    }
+
+    fn head(env: &SnakeEnv) -> Position {
+        env.sim.snake.body[0]
+    }
+
+    #[test]
+    fn reset_is_deterministic_per_seed() {
+        let actions = [Action::Straight, Action::TurnLeft, Action::Straight, Action::TurnRight, Action::TurnRight];
+        let play = |seed: u64| {
+            let mut env = env(Board::new(16, 12), BoundaryRule::Wrap);
+            let mut observations = vec![env.reset(seed)];
+            for action in actions {
+                observations.push(env.step(action).0);
+            }
+            (observations, env.sim.food.position, env.sim.tick)
+        };
+        assert_eq!(play(9), play(9));
+
+        // reset ซ้ำด้วย seed เดิมกลับไปที่สถานะเริ่มต้นเดียวกัน
+        let mut env = env(Board::new(16, 12), BoundaryRule::Wrap);
+        let first = env.reset(9);
+        env.step(Action::TurnLeft);
+        assert_eq!(env.reset(9), first);
+        assert_eq!(env.sim.tick, 0);
+    }
+
+    #[test]
+    fn step_rewards_food_death_and_moves() {
+        let mut env = env(Board::new(10, 8), BoundaryRule::Solid);
+        env.reset(1);
+        place_food(&mut env, Position { x: 0, y: 0 });
+        let (_, reward, done, info) = env.step(Action::Straight);
+        assert_eq!(reward, -0.01);
+        assert!(!done);
+        assert!(info.events.contains(&GameEvent::Moved));
+
+        let ahead = Direction::Right.offset(head(&env));
+        place_food(&mut env, ahead);
+        let (_, reward, _, info) = env.step(Action::Straight);
+        assert_eq!(reward, 2.0 - 0.01);
+        assert_eq!((info.food_eaten, info.score), (1, 1));
+
+        // เดินตรงไปชนขอบขวา ตอนตายไม่นับรางวัลการขยับ
+        place_food(&mut env, Position { x: 0, y: 0 });
+        let mut last = (0.0, false);
+        while !env.is_done() {
+            let (_, reward, done, _) = env.step(Action::Straight);
+            last = (reward, done);
+        }
+        assert_eq!(last, (-3.0, true));
+        assert_eq!(env.sim.death_cause, Some(DeathCause::Wall));
+    }
+
+    #[test]
+    fn truncates_after_moves_without_food() {
+        let mut env = env(Board::new(20, 20), BoundaryRule::Wrap);
+        env.max_moves_without_food = 3;
+        env.reset(2);
+        let below = Direction::Down.offset(head(&env));
+        place_food(&mut env, below);
+        for _ in 0..2 {
+            let (_, reward, done, info) = env.step(Action::Straight);
+            assert_eq!(reward, -0.01);
+            assert!(!done && !info.truncated);
+        }
+        let (_, reward, done, info) = env.step(Action::Straight);
+        assert_eq!(reward, -0.01 - 0.5);
+        assert!(done && info.truncated);
+        assert_eq!(info.death_cause, None);
+        assert!(!env.sim.is_over());
+
+        // reset แล้วเริ่มนับใหม่
+        env.reset(2);
+        assert!(!env.is_done());
+    }
+
+    #[test]
+    fn stays_done_after_death() {
+        let mut env = env(Board::new(10, 8), BoundaryRule::Solid);
+        env.reset(3);
+        place_food(&mut env, Position { x: 0, y: 0 });
+        while !env.is_done() {
+            env.step(Action::Straight);
+        }
+        let tick = env.sim.tick;
+        let before = env.observe();
+        let (observation, reward, done, info) = env.step(Action::TurnLeft);
+        assert!(done);
+        assert_eq!(reward, 0.0);
+        assert!(info.events.is_empty());
+        assert!(info.death_cause.is_some());
+        assert_eq!(observation, before);
+        assert_eq!(env.sim.tick, tick);
+    }
+
+    #[test]
+    fn observation_sizes_follow_the_board() {
+        let board = Board::new(13, 9);
+        let mut env = env(board, BoundaryRule::Wrap);
+        let observation = env.reset(4);
+        assert_eq!(env.grid_shape(), (GRID_CHANNELS, 9, 13));
+        assert_eq!(observation.grid.len(), GRID_CHANNELS * 9 * 13);
+        assert_eq!(observation.features.len(), FEATURE_COUNT);
+
+        // ช่องหัวงูมีค่าเดียวตรงตำแหน่งหัว เรียงแบบ [channel][y][x]
+        let plane = board.cell_count();
+        let head_plane = &observation.grid[plane..2 * plane];
+        assert_eq!(head_plane.iter().filter(|value| **value == 1.0).count(), 1);
+        let position = head(&env);
+        assert_eq!(head_plane[(position.y * board.width + position.x) as usize], 1.0);
+    }
+}
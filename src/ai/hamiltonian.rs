@@ -0,0 +1,323 @@
+use crate::ai::autopilot::Autopilot;
+use crate::controller::{self, Controller};
+use crate::game::board::Board;
+use crate::game::config::{BoundaryRule, GameConfig};
+use crate::game::game_state::{Direction, Position};
+use crate::game::simulation::Simulation;
+
+// ไม่ตัดทางลัดเมื่องูยาวเกินสัดส่วนนี้ของวงรอบ เดินตามวงอย่างเดียวจนจบ
+const SHORTCUT_LIMIT: f32 = 0.5;
+// ช่องว่างที่เผื่อไว้ระหว่างหัวกับหางเวลาตัดทางลัด กันงูยาวขึ้นแล้วชนหาง
+const SHORTCUT_MARGIN: usize = 4;
+
+// วงรอบที่ผ่านทุกช่องว่างบนกระดานช่องละครั้งพอดี
+pub struct HamiltonianCycle {
+    board: Board,
+    cells: Vec<Position>,
+    // ลำดับของแต่ละช่องในวง (None = ช่องที่ถูกกั้น)
+    order: Vec<Option<usize>>,
+}
+
+impl HamiltonianCycle {
+    // สร้างวงรอบสำหรับกระดานที่มีช่องกั้นตาม blocked คืนค่า None ถ้าสร้างไม่ได้
+    // กระดานโล่ง: ด้านใดด้านหนึ่งเป็นเลขคู่ หรือทะลุขอบได้ทั้งสองด้าน
+    // มีสิ่งกีดขวาง: กว้างและสูงเป็นเลขคู่ และสิ่งกีดขวางเต็มบล็อก 2x2 พอดี
+    pub fn build(board: &Board, boundary: BoundaryRule, blocked: &[bool]) -> Option<Self> {
+        let open = !blocked.iter().any(|b| *b);
+        let cells = if open && (board.width % 2 == 0 || board.height % 2 == 0) {
+            serpentine(board)
+        } else if open && boundary == BoundaryRule::Wrap {
+            odd_torus(board)
+        } else if board.width % 2 == 0 && board.height % 2 == 0 {
+            block_cycle(board, blocked)?
+        } else {
+            return None;
+        };
+
+        let mut order = vec![None; board.cell_count()];
+        for (i, cell) in cells.iter().enumerate() {
+            order[board.index(cell)] = Some(i);
+        }
+        let cycle = HamiltonianCycle { board: *board, cells, order };
+        cycle.is_valid(boundary, blocked).then_some(cycle)
+    }
+
+    // ทุกช่องว่างอยู่ในวงครั้งเดียว และช่องติดกันในวงเดินถึงกันได้จริง
+    fn is_valid(&self, boundary: BoundaryRule, blocked: &[bool]) -> bool {
+        let free = blocked.iter().filter(|b| !**b).count();
+        let covered = self.order.iter().filter(|o| o.is_some()).count();
+        if self.cells.len() < 2 || covered != self.cells.len() || covered != free {
+            return false;
+        }
+        self.cells.iter().enumerate().all(|(i, cell)| {
+            let next = self.cells[(i + 1) % self.cells.len()];
+            !blocked[self.board.index(cell)]
+                && Direction::ALL
+                    .iter()
+                    .any(|direction| self.board.neighbor(*cell, *direction, boundary) == Some(next))
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn cells(&self) -> &[Position] {
+        &self.cells
+    }
+
+    pub fn order(&self, position: &Position) -> Option<usize> {
+        if !self.board.contains(position) {
+            return None;
+        }
+        self.order[self.board.index(position)]
+    }
+
+    // จำนวนก้าวตามวงจาก from ไป to
+    pub fn distance(&self, from: usize, to: usize) -> usize {
+        (to + self.cells.len() - from) % self.cells.len()
+    }
+}
+
+// งูเดินแบบงูเลื้อยทีละแถว ใช้คอลัมน์ซ้ายสุดเป็นทางกลับ (ต้องมีจำนวนแถวเป็นเลขคู่)
+fn serpentine(board: &Board) -> Vec<Position> {
+    // ถ้าจำนวนแถวเป็นเลขคี่ สร้างแบบสลับแกนแล้วสลับกลับ
+    let transpose = board.height % 2 != 0;
+    let (width, height) = if transpose { (board.height, board.width) } else { (board.width, board.height) };
+    let mut cells = Vec::new();
+    for y in 0..height {
+        if y % 2 == 0 {
+            cells.extend((1..width).map(|x| (x, y)));
+        } else {
+            cells.extend((1..width).rev().map(|x| (x, y)));
+        }
+    }
+    cells.extend((0..height).rev().map(|y| (0, y)));
+    cells
+        .into_iter()
+        .map(|(x, y)| if transpose { Position { x: y, y: x } } else { Position { x, y } })
+        .collect()
+}
+
+// กระดานทะลุขอบที่กว้างและสูงเป็นเลขคี่ทั้งคู่: เดินครบแถวแล้วลงแถวถัดไป
+// แถวที่เดินขวาจุดเริ่มเลื่อนซ้ายหนึ่งช่อง แถวที่เดินซ้ายเลื่อนขวา
+// เลือกจำนวนแถวแต่ละแบบให้เลื่อนรวมครบหนึ่งรอบพอดีเมื่อกลับถึงแถวแรก
+fn odd_torus(board: &Board) -> Vec<Position> {
+    let transpose = board.height < board.width;
+    let (width, height) = if transpose { (board.height, board.width) } else { (board.width, board.height) };
+    let rightward_rows = (height + width) / 2;
+    let mut cells = Vec::new();
+    let mut start = 0;
+    for y in 0..height {
+        let step = if y < rightward_rows { 1 } else { -1 };
+        cells.extend((0..width).map(|k| ((start + step * k).rem_euclid(width), y)));
+        start = (start - step).rem_euclid(width);
+    }
+    cells
+        .into_iter()
+        .map(|(x, y)| if transpose { Position { x: y, y: x } } else { Position { x, y } })
+        .collect()
+}
+
+// แบ่งกระดานเป็นบล็อก 2x2 สร้าง spanning tree บนบล็อกที่ว่าง แล้วเดินเลียบต้นไม้
+// บล็อกที่ว่างไม่ครบทั้งสี่ช่องใช้ไม่ได้
+fn block_cycle(board: &Board, blocked: &[bool]) -> Option<Vec<Position>> {
+    let (blocks_w, blocks_h) = (board.width / 2, board.height / 2);
+    let block_index = |bx: i32, by: i32| (by * blocks_w + bx) as usize;
+    let mut open = vec![false; (blocks_w * blocks_h) as usize];
+    for by in 0..blocks_h {
+        for bx in 0..blocks_w {
+            let count = [(0, 0), (1, 0), (0, 1), (1, 1)]
+                .iter()
+                .filter(|(dx, dy)| !blocked[board.index(&Position { x: bx * 2 + dx, y: by * 2 + dy })])
+                .count();
+            match count {
+                0 => {}
+                4 => open[block_index(bx, by)] = true,
+                _ => return None,
+            }
+        }
+    }
+
+    let root = open.iter().position(|o| *o)?;
+    let root = (root as i32 % blocks_w, root as i32 / blocks_w);
+    // right[b] = มีทางเชื่อมกับบล็อกขวา, down[b] = มีทางเชื่อมกับบล็อกล่าง
+    let mut right = vec![false; open.len()];
+    let mut down = vec![false; open.len()];
+    let mut visited = vec![false; open.len()];
+    visited[block_index(root.0, root.1)] = true;
+    let mut stack = vec![root];
+    while let Some(&(bx, by)) = stack.last() {
+        let next = [(1, 0), (0, 1), (-1, 0), (0, -1)].iter().map(|(dx, dy)| (bx + dx, by + dy)).find(|&(nx, ny)| {
+            nx >= 0 && ny >= 0 && nx < blocks_w && ny < blocks_h && open[block_index(nx, ny)] && !visited[block_index(nx, ny)]
+        });
+        match next {
+            Some((nx, ny)) => {
+                visited[block_index(nx, ny)] = true;
+                match (nx - bx, ny - by) {
+                    (1, 0) => right[block_index(bx, by)] = true,
+                    (-1, 0) => right[block_index(nx, ny)] = true,
+                    (0, 1) => down[block_index(bx, by)] = true,
+                    _ => down[block_index(nx, ny)] = true,
+                }
+                stack.push((nx, ny));
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+    if open.iter().zip(&visited).any(|(o, v)| *o && !*v) {
+        return None;
+    }
+
+    // ในแต่ละบล็อกวนทวนเข็ม ซ้ายบน → ซ้ายล่าง → ขวาล่าง → ขวาบน
+    // ถ้ามีทางเชื่อมกับบล็อกข้าง ๆ ให้ออกไปทางนั้นแทน
+    let next_cell = |position: Position| {
+        let (bx, by) = (position.x / 2, position.y / 2);
+        let linked_left = bx > 0 && right[block_index(bx - 1, by)];
+        let linked_up = by > 0 && down[block_index(bx, by - 1)];
+        let direction = match (position.x % 2, position.y % 2) {
+            (0, 0) if linked_left => Direction::Left,
+            (0, 0) => Direction::Down,
+            (0, _) if down[block_index(bx, by)] => Direction::Down,
+            (0, _) => Direction::Right,
+            (_, 1) if right[block_index(bx, by)] => Direction::Right,
+            (_, 1) => Direction::Up,
+            _ if linked_up => Direction::Up,
+            _ => Direction::Left,
+        };
+        direction.offset(position)
+    };
+
+    let start = Position { x: root.0 * 2, y: root.1 * 2 };
+    let mut cells = vec![start];
+    let mut current = next_cell(start);
+    while current != start && cells.len() < board.cell_count() {
+        cells.push(current);
+        current = next_cell(current);
+    }
+    Some(cells)
+}
+
+// เล่นให้เต็มกระดานโดยเดินตามวงรอบ Hamiltonian และตัดทางลัดเมื่อปลอดภัย
+// ถ้าสร้างวงไม่ได้ หรือตัวงูไม่ได้เรียงตามวง (เช่นเขาวงกตเพิ่งเปลี่ยน) ใช้ Autopilot แทน
+pub struct HamiltonianSolver {
+    pub shortcuts: bool,
+    cycle: Option<HamiltonianCycle>,
+    // ช่องที่กั้นอยู่ตอนสร้างวงล่าสุด ถ้าเปลี่ยนต้องสร้างใหม่
+    built_for: Option<(Board, BoundaryRule, Vec<bool>)>,
+    fallback: Autopilot,
+}
+
+impl Default for HamiltonianSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HamiltonianSolver {
+    pub fn new() -> Self {
+        HamiltonianSolver {
+            shortcuts: true,
+            cycle: None,
+            built_for: None,
+            fallback: Autopilot::new(),
+        }
+    }
+
+    // วงรอบที่ใช้อยู่ (None = กระดานนี้สร้างวงไม่ได้)
+    pub fn cycle(&self) -> Option<&HamiltonianCycle> {
+        self.cycle.as_ref()
+    }
+
+    // สร้างวงใหม่ถ้ากระดานเปลี่ยน hazard ทุกช่องที่อาจไปอยู่นับเป็นช่องกั้น
+    pub fn prepare(&mut self, sim: &Simulation) {
+        let board = sim.config.board;
+        let mut blocked = vec![false; board.cell_count()];
+        for obstacle in &sim.obstacles {
+            blocked[board.index(obstacle)] = true;
+        }
+        for hazard in &sim.hazards {
+            for cell in hazard.spec.zone() {
+                if board.contains(cell) {
+                    blocked[board.index(cell)] = true;
+                }
+            }
+        }
+        let key = (board, sim.config.boundary, blocked);
+        if self.built_for.as_ref() != Some(&key) {
+            self.cycle = HamiltonianCycle::build(&key.0, key.1, &key.2);
+            self.built_for = Some(key);
+        }
+    }
+
+    pub fn choose_direction(&mut self, sim: &Simulation) -> Option<Direction> {
+        self.prepare(sim);
+        match &self.cycle {
+            Some(cycle) => match self.cycle_direction(cycle, sim) {
+                Some(direction) => Some(direction),
+                None => self.fallback.choose_direction(sim),
+            },
+            None => self.fallback.choose_direction(sim),
+        }
+    }
+
+    fn cycle_direction(&self, cycle: &HamiltonianCycle, sim: &Simulation) -> Option<Direction> {
+        let body = &sim.snake.body;
+        let mut orders = Vec::with_capacity(body.len());
+        for segment in body {
+            orders.push(cycle.order(segment)?);
+        }
+
+        // ตัวงูต้องเรียงตามวงจากหางไปหัว ทางลัดถึงจะไม่ตัดผ่านลำตัว
+        let span: usize = orders.windows(2).map(|pair| cycle.distance(pair[1], pair[0])).sum();
+        if orders.windows(2).any(|pair| pair[0] == pair[1]) || span >= cycle.len() {
+            return None;
+        }
+
+        let head = orders[0];
+        let tail_distance = if body.len() > 1 { cycle.distance(head, orders[orders.len() - 1]) } else { cycle.len() };
+        let food_distance = cycle.order(&sim.food.position).map_or(1, |food| cycle.distance(head, food));
+        let can_shortcut = self.shortcuts && (body.len() as f32) < cycle.len() as f32 * SHORTCUT_LIMIT;
+
+        let candidates: Vec<(Direction, usize)> = Direction::ALL
+            .into_iter()
+            .filter(|direction| *direction != sim.snake.direction.opposite())
+            .filter_map(|direction| {
+                let next = sim.config.board.neighbor(body[0], direction, sim.config.boundary)?;
+                let distance = cycle.distance(head, cycle.order(&next)?);
+                let allowed = distance == 1 || (can_shortcut && distance + SHORTCUT_MARGIN < tail_distance);
+                (allowed && distance < tail_distance).then_some((direction, distance))
+            })
+            .collect();
+
+        // เลือกช่องที่พาไปใกล้อาหารที่สุดตามวงโดยไม่ข้ามอาหาร
+        // ถ้าทุกทางข้ามอาหาร (งูยาวหนึ่งช่องและหันผิดทาง) เลือกทางที่ข้ามน้อยที่สุด
+        candidates
+            .iter()
+            .filter(|(_, distance)| *distance <= food_distance)
+            .max_by_key(|(_, distance)| *distance)
+            .or_else(|| candidates.iter().min_by_key(|(_, distance)| *distance))
+            .map(|(direction, _)| *direction)
+    }
+}
+
+impl Controller for HamiltonianSolver {
+    fn next_direction(&mut self, sim: &Simulation) -> Option<Direction> {
+        controller::plan_on_move(sim, |sim| self.choose_direction(sim))
+    }
+
+    fn name(&self) -> &'static str {
+        "SOLVER"
+    }
+}
+
+// เล่นหนึ่งเกมด้วย HamiltonianSolver จนจบหรือครบ max_ticks
+pub fn run_solver(config: GameConfig, seed: u64, max_ticks: u64) -> Simulation {
+    controller::play(&mut HamiltonianSolver::new(), config, seed, max_ticks)
+}
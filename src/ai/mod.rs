@@ -0,0 +1,7 @@
+pub mod autopilot;
+pub mod env;
+pub mod hamiltonian;
+
+pub use autopilot::{run_autopilot, Autopilot};
+pub use env::{Action, Observation, RewardConfig, SnakeEnv, StepInfo, FEATURE_COUNT, GRID_CHANNELS};
+pub use hamiltonian::{run_solver, HamiltonianCycle, HamiltonianSolver};
@@ -0,0 +1,134 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
+use std::rc::Rc;
+use macroquad::audio::{load_sound, load_sound_from_bytes, play_sound, PlaySoundParams, Sound};
+use crate::audio::mixer::SoundCategory;
+use crate::audio::synth::{encode_wav, synthesize, SynthParams, EAT_PITCH_STEPS};
+use crate::game::PowerUpType;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum SoundEffect {
+    Eat,
+    Crash,
+    PowerUp(PowerUpType),
+    PowerUpExpired(PowerUpType),
+    MenuClick,
+    Pause,
+    // เต็มกระดาน
+    Victory,
+    LevelComplete,
+}
+
+impl SoundEffect {
+    pub const ALL: [SoundEffect; 12] = [
+        SoundEffect::Eat,
+        SoundEffect::Crash,
+        SoundEffect::PowerUp(PowerUpType::SpeedBoost),
+        SoundEffect::PowerUp(PowerUpType::Shrink),
+        SoundEffect::PowerUp(PowerUpType::GhostMode),
+        SoundEffect::PowerUpExpired(PowerUpType::SpeedBoost),
+        SoundEffect::PowerUpExpired(PowerUpType::Shrink),
+        SoundEffect::PowerUpExpired(PowerUpType::GhostMode),
+        SoundEffect::MenuClick,
+        SoundEffect::Pause,
+        SoundEffect::Victory,
+        SoundEffect::LevelComplete,
+    ];
+
+    pub fn category(self) -> SoundCategory {
+        SoundCategory::Effects
+    }
+
+    pub fn file_name(self) -> &'static str {
+        match self {
+            SoundEffect::Eat => "eat.wav",
+            SoundEffect::Crash => "crash.wav",
+            SoundEffect::PowerUp(PowerUpType::SpeedBoost) => "speed_boost.wav",
+            SoundEffect::PowerUp(PowerUpType::Shrink) => "shrink.wav",
+            SoundEffect::PowerUp(PowerUpType::GhostMode) => "ghost_mode.wav",
+            SoundEffect::PowerUpExpired(PowerUpType::SpeedBoost) => "speed_boost_end.wav",
+            SoundEffect::PowerUpExpired(PowerUpType::Shrink) => "shrink_end.wav",
+            SoundEffect::PowerUpExpired(PowerUpType::GhostMode) => "ghost_mode_end.wav",
+            SoundEffect::MenuClick => "menu_click.wav",
+            SoundEffect::Pause => "pause.wav",
+            SoundEffect::Victory => "victory.wav",
+            SoundEffect::LevelComplete => "level_complete.wav",
+        }
+    }
+}
+
+// variant ใช้เลือกเสียงย่อยของ effect เดียวกัน (เช่นระดับเสียงตอนกินอาหาร)
+pub trait AudioBackend {
+    fn play(&mut self, effect: SoundEffect, variant: usize, volume: f32);
+}
+
+// ไม่เล่นเสียงจริง แค่จดไว้ว่าเล่นอะไรไปบ้าง ใช้ตอนไม่มีอุปกรณ์เสียงหรือใน test
+// clone แล้วรายการยังเป็นชุดเดียวกัน จึงอ่านได้หลังส่งตัวหนึ่งให้ SoundManager ไปแล้ว
+#[derive(Clone, Default)]
+pub struct NullBackend {
+    pub played: Rc<RefCell<Vec<(SoundEffect, usize)>>>,
+}
+
+impl AudioBackend for NullBackend {
+    fn play(&mut self, effect: SoundEffect, variant: usize, _volume: f32) {
+        self.played.borrow_mut().push((effect, variant));
+    }
+}
+
+fn synthesized_variants(effect: SoundEffect) -> Vec<SynthParams> {
+    match effect {
+        SoundEffect::Eat => (0..EAT_PITCH_STEPS).map(SynthParams::eat).collect(),
+        _ => vec![SynthParams::for_effect(effect)],
+    }
+}
+
+pub struct MacroquadBackend {
+    sounds: HashMap<SoundEffect, Vec<Sound>>,
+}
+
+impl MacroquadBackend {
+    // ใช้ไฟล์ใน dir ถ้ามี ไม่งั้นสังเคราะห์เสียงขึ้นมาเอง
+    pub async fn load(dir: &Path) -> Self {
+        let mut sounds = HashMap::new();
+        for effect in SoundEffect::ALL {
+            let path = dir.join(effect.file_name());
+            if path.exists() {
+                match load_sound(&path.to_string_lossy()).await {
+                    Ok(sound) => {
+                        sounds.insert(effect, vec![sound]);
+                        continue;
+                    }
+                    Err(e) => eprintln!("Could not load sound {}: {}", path.display(), e),
+                }
+            }
+
+            let mut variants = Vec::new();
+            for params in synthesized_variants(effect) {
+                let wav = encode_wav(&synthesize(&params));
+                match load_sound_from_bytes(&wav).await {
+                    Ok(sound) => variants.push(sound),
+                    Err(e) => eprintln!("Could not create sound {:?}: {}", effect, e),
+                }
+            }
+            if !variants.is_empty() {
+                sounds.insert(effect, variants);
+            }
+        }
+        MacroquadBackend { sounds }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sounds.is_empty()
+    }
+}
+
+impl AudioBackend for MacroquadBackend {
+    fn play(&mut self, effect: SoundEffect, variant: usize, volume: f32) {
+        if let Some(variants) = self.sounds.get(&effect) {
+            if let Some(sound) = variants.get(variant.min(variants.len() - 1)) {
+                play_sound(sound, PlaySoundParams { looped: false, volume });
+            }
+        }
+    }
+}
@@ -0,0 +1,38 @@
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SoundCategory {
+    Music,
+    Effects,
+}
+
+// ความดังรวมของแต่ละหมวด = master * หมวด (หรือ 0 ถ้าปิดเสียง)
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct VolumeMixer {
+    pub master: f32,
+    pub music: f32,
+    pub effects: f32,
+    pub muted: bool,
+}
+
+impl Default for VolumeMixer {
+    fn default() -> Self {
+        VolumeMixer {
+            master: 1.0,
+            music: 0.7,
+            effects: 1.0,
+            muted: false,
+        }
+    }
+}
+
+impl VolumeMixer {
+    pub fn volume(&self, category: SoundCategory) -> f32 {
+        if self.muted {
+            return 0.0;
+        }
+        let category_volume = match category {
+            SoundCategory::Music => self.music,
+            SoundCategory::Effects => self.effects,
+        };
+        (self.master * category_volume).clamp(0.0, 1.0)
+    }
+}
@@ -1,3 +1,11 @@
+pub mod backend;
+pub mod mixer;
+pub mod music;
 pub mod sound_manager;
+pub mod synth;
 
-pub use sound_manager::SoundManager; 
\ No newline at end of file
+pub use backend::{AudioBackend, MacroquadBackend, NullBackend, SoundEffect};
+pub use mixer::{SoundCategory, VolumeMixer};
+pub use music::{MusicManager, MusicTrack};
+pub use sound_manager::SoundManager;
+pub use synth::{SynthParams, Waveform};
\ No newline at end of file
@@ -0,0 +1,317 @@
+use macroquad::audio::{load_sound_from_bytes, play_sound, set_sound_volume, PlaySoundParams, Sound};
+use crate::audio::mixer::{SoundCategory, VolumeMixer};
+use crate::audio::synth::{encode_wav, render_notes, Note, SynthParams, Waveform};
+use crate::game::GameState;
+
+const CROSSFADE_SECONDS: f32 = 1.0;
+// ช่วง intensity ที่ layer หนึ่งใช้ค่อย ๆ ดังขึ้นจนเต็ม
+const INTENSITY_RAMP: f32 = 0.2;
+// งูยาวเท่านี้ถือว่าเพลงเข้มข้นเต็มที่
+const FULL_INTENSITY_LENGTH: usize = 30;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum MusicTrack {
+    Menu,
+    Playing,
+    GameOver,
+}
+
+impl MusicTrack {
+    pub fn for_state(state: &GameState) -> Option<Self> {
+        match state {
+            GameState::Menu | GameState::Leaderboard | GameState::Settings | GameState::LevelSelect | GameState::Victory => {
+                Some(MusicTrack::Menu)
+            }
+            GameState::Playing | GameState::Replay => Some(MusicTrack::Playing),
+            GameState::GameOver => Some(MusicTrack::GameOver),
+            GameState::Paused => None,
+        }
+    }
+}
+
+// ส่วนหนึ่งของเพลง ทุก layer เล่นวนพร้อมกันตลอด แค่ปรับความดังขึ้นลง
+pub struct MusicLayer {
+    pub track: MusicTrack,
+    // intensity ขั้นต่ำที่ layer นี้เริ่มดัง (None = ดังตลอดเมื่อเล่น track นี้)
+    pub threshold: Option<f32>,
+    pub volume: f32,
+    sound: Option<Sound>,
+}
+
+impl MusicLayer {
+    fn gain(&self, intensity: f32) -> f32 {
+        match self.threshold {
+            None => 1.0,
+            Some(threshold) => ((intensity - threshold) / INTENSITY_RAMP).clamp(0.0, 1.0),
+        }
+    }
+}
+
+pub struct MusicManager {
+    pub layers: Vec<MusicLayer>,
+    pub current: Option<MusicTrack>,
+    pub intensity: f32,
+    pub mixer: VolumeMixer,
+}
+
+impl Default for MusicManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MusicManager {
+    // ไม่มีเสียงจริง แต่ยังคำนวณความดังของแต่ละ layer ได้ตามปกติ
+    pub fn new() -> Self {
+        let layers = compose()
+            .into_iter()
+            .map(|spec| MusicLayer {
+                track: spec.track,
+                threshold: spec.threshold,
+                volume: 0.0,
+                sound: None,
+            })
+            .collect();
+
+        MusicManager {
+            layers,
+            current: None,
+            intensity: 0.0,
+            mixer: VolumeMixer::default(),
+        }
+    }
+
+    pub async fn load() -> Self {
+        if !cfg!(feature = "audio") {
+            return Self::new();
+        }
+
+        let mut layers = Vec::new();
+        for spec in compose() {
+            let samples = render_notes(&spec.notes, &spec.instrument, spec.bpm, spec.beats);
+            let sound = match load_sound_from_bytes(&encode_wav(&samples)).await {
+                Ok(sound) => {
+                    play_sound(&sound, PlaySoundParams { looped: true, volume: 0.0 });
+                    Some(sound)
+                }
+                Err(e) => {
+                    eprintln!("Could not create music layer: {}", e);
+                    None
+                }
+            };
+            layers.push(MusicLayer {
+                track: spec.track,
+                threshold: spec.threshold,
+                volume: 0.0,
+                sound,
+            });
+        }
+
+        MusicManager {
+            layers,
+            current: None,
+            intensity: 0.0,
+            mixer: VolumeMixer::default(),
+        }
+    }
+
+    pub fn set_track(&mut self, track: Option<MusicTrack>) {
+        self.current = track;
+    }
+
+    pub fn set_intensity(&mut self, speed_multiplier: f32, snake_length: usize) {
+        let length_factor = snake_length.saturating_sub(1) as f32 / FULL_INTENSITY_LENGTH as f32;
+        let speed_factor = (speed_multiplier - 1.0).max(0.0);
+        self.intensity = (length_factor + speed_factor).min(1.0);
+    }
+
+    pub fn target_volume(&self, layer: &MusicLayer) -> f32 {
+        if Some(layer.track) == self.current {
+            self.mixer.volume(SoundCategory::Music) * layer.gain(self.intensity)
+        } else {
+            0.0
+        }
+    }
+
+    // ค่อย ๆ เลื่อนความดังแต่ละ layer เข้าหาเป้าหมาย (crossfade)
+    pub fn update(&mut self, dt: f32) {
+        let max_change = dt / CROSSFADE_SECONDS;
+        for i in 0..self.layers.len() {
+            let target = self.target_volume(&self.layers[i]);
+            let layer = &mut self.layers[i];
+            let volume = layer.volume + (target - layer.volume).clamp(-max_change, max_change);
+            if volume != layer.volume {
+                layer.volume = volume;
+                if let Some(sound) = &layer.sound {
+                    set_sound_volume(sound, volume);
+                }
+            }
+        }
+    }
+}
+
+struct LayerSpec {
+    track: MusicTrack,
+    threshold: Option<f32>,
+    notes: Vec<Note>,
+    instrument: SynthParams,
+    bpm: f32,
+    beats: f32,
+}
+
+fn layer(
+    track: MusicTrack,
+    threshold: Option<f32>,
+    pattern: &[(u8, f32, f32)],
+    instrument: SynthParams,
+    bpm: f32,
+    beats: f32,
+) -> LayerSpec {
+    let notes = pattern
+        .iter()
+        .map(|&(pitch, start, length)| Note { pitch, start, length })
+        .collect();
+    LayerSpec {
+        track,
+        threshold,
+        notes,
+        instrument,
+        bpm,
+        beats,
+    }
+}
+
+// ลำดับคอร์ด C - Am - F - G บาร์ละ 4 จังหวะ
+const PROGRESSION: [[u8; 3]; 4] = [[60, 64, 67], [57, 60, 64], [53, 57, 60], [55, 59, 62]];
+
+// แต่ง layer ทั้งหมดของทุก track
+fn compose() -> Vec<LayerSpec> {
+    let mut layers = Vec::new();
+
+    // เมนู: arpeggio ช้า ๆ
+    let mut menu = Vec::new();
+    for (bar, chord) in PROGRESSION.iter().enumerate() {
+        for step in 0..8 {
+            let pitch = chord[step % 3] + if step >= 4 { 12 } else { 0 };
+            menu.push((pitch, bar as f32 * 4.0 + step as f32 * 0.5, 0.5));
+        }
+    }
+    let pad = SynthParams::new(Waveform::Triangle, 1.0, 1.0).envelope(0.01, 0.0, 0.25).volume(0.25);
+    layers.push(layer(MusicTrack::Menu, None, &menu, pad, 90.0, 16.0));
+
+    // ระหว่างเล่น: bass ตลอด, ทำนองเมื่อเริ่มมัน, กลองเมื่อเร็ว/ยาวมาก
+    let mut bass = Vec::new();
+    let mut lead = Vec::new();
+    let mut drums = Vec::new();
+    for (bar, chord) in PROGRESSION.iter().enumerate() {
+        let start = bar as f32 * 4.0;
+        for beat in 0..4 {
+            bass.push((chord[0] - 24, start + beat as f32, 0.5));
+        }
+        for step in 0..8 {
+            let pitch = chord[(step * 2) % 3] + 12;
+            lead.push((pitch, start + step as f32 * 0.5, 0.25));
+            drums.push((if step % 2 == 0 { 40 } else { 90 }, start + step as f32 * 0.5, 0.1));
+        }
+    }
+    let bass_voice = SynthParams::new(Waveform::Square, 1.0, 1.0).envelope(0.0, 0.0, 0.1).volume(0.2);
+    let lead_voice = SynthParams::new(Waveform::Square, 1.0, 1.0).envelope(0.0, 0.0, 0.08).volume(0.12);
+    let drum_voice = SynthParams::new(Waveform::Noise, 1.0, 0.5).envelope(0.0, 0.0, 0.06).volume(0.2);
+    layers.push(layer(MusicTrack::Playing, None, &bass, bass_voice, 120.0, 16.0));
+    layers.push(layer(MusicTrack::Playing, Some(0.1), &lead, lead_voice, 120.0, 16.0));
+    layers.push(layer(MusicTrack::Playing, Some(0.5), &drums, drum_voice, 120.0, 16.0));
+
+    // จบเกม: ทำนองไมเนอร์ไล่ลง
+    let game_over = [(64, 0.0, 1.0), (62, 1.0, 1.0), (60, 2.0, 1.0), (59, 3.0, 1.0), (57, 4.0, 4.0)];
+    let voice = SynthParams::new(Waveform::Sine, 1.0, 1.0).envelope(0.05, 0.0, 0.4).volume(0.3);
+    layers.push(layer(MusicTrack::GameOver, None, &game_over, voice, 70.0, 8.0));
+
+    layers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: f32 = 0.1;
+
+    fn manager() -> MusicManager {
+        let mut music = MusicManager::new();
+        music.mixer.music = 1.0;
+        music
+    }
+
+    // เดินแบบเดียวกับ update_music ในเกม ทีละ DT วินาที
+    fn play(music: &mut MusicManager, state: GameState, snake_length: usize, seconds: f32) {
+        for _ in 0..(seconds / DT).round() as usize {
+            music.set_track(MusicTrack::for_state(&state));
+            music.set_intensity(1.0, snake_length);
+            music.update(DT);
+        }
+    }
+
+    fn volumes(music: &MusicManager, track: MusicTrack) -> Vec<f32> {
+        music.layers.iter().filter(|layer| layer.track == track).map(|layer| layer.volume).collect()
+    }
+
+    fn assert_close(actual: &[f32], expected: &[f32]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-4, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn crossfades_between_tracks() {
+        let mut music = manager();
+        play(&mut music, GameState::Menu, 1, 0.5);
+        assert_close(&volumes(&music, MusicTrack::Menu), &[0.5]);
+        play(&mut music, GameState::Menu, 1, 1.0);
+        assert_close(&volumes(&music, MusicTrack::Menu), &[1.0]);
+
+        // เปลี่ยนไปจบเกม เพลงเก่าค่อย ๆ เบาลงพร้อมกับเพลงใหม่ดังขึ้น
+        play(&mut music, GameState::GameOver, 1, 0.3);
+        assert_close(&volumes(&music, MusicTrack::Menu), &[0.7]);
+        assert_close(&volumes(&music, MusicTrack::GameOver), &[0.3]);
+        play(&mut music, GameState::GameOver, 1, 1.0);
+        assert_close(&volumes(&music, MusicTrack::Menu), &[0.0]);
+        assert_close(&volumes(&music, MusicTrack::GameOver), &[1.0]);
+
+        // หยุดเกมชั่วคราวแล้วเพลงเบาลงจนเงียบ
+        play(&mut music, GameState::Paused, 1, 1.0);
+        assert!(music.layers.iter().all(|layer| layer.volume == 0.0));
+    }
+
+    #[test]
+    fn follows_the_mixer_volume() {
+        let mut music = manager();
+        music.mixer.music = 0.4;
+        play(&mut music, GameState::Menu, 1, 2.0);
+        assert_close(&volumes(&music, MusicTrack::Menu), &[0.4]);
+        music.mixer.muted = true;
+        play(&mut music, GameState::Menu, 1, 0.2);
+        assert_close(&volumes(&music, MusicTrack::Menu), &[0.2]);
+    }
+
+    #[test]
+    fn adds_layers_as_the_game_heats_up() {
+        let mut music = manager();
+        // งูยาว 1 เล่นแค่ bass
+        play(&mut music, GameState::Playing, 1, 2.0);
+        assert_close(&volumes(&music, MusicTrack::Playing), &[1.0, 0.0, 0.0]);
+
+        // intensity 0.2 ทำนองดังครึ่งหนึ่ง (เริ่มที่ 0.1 ไต่ขึ้นภายใน 0.2)
+        play(&mut music, GameState::Playing, 7, 2.0);
+        assert_close(&volumes(&music, MusicTrack::Playing), &[1.0, 0.5, 0.0]);
+
+        // ยาวเต็มที่ทุก layer ดังเต็ม แต่ค่อย ๆ ไต่ขึ้นตามเวลา crossfade
+        play(&mut music, GameState::Playing, 31, 0.5);
+        assert_close(&volumes(&music, MusicTrack::Playing), &[1.0, 1.0, 0.5]);
+        play(&mut music, GameState::Playing, 31, 1.0);
+        assert_close(&volumes(&music, MusicTrack::Playing), &[1.0, 1.0, 1.0]);
+
+        // replay ใช้เพลงเดียวกับตอนเล่น
+        assert_eq!(MusicTrack::for_state(&GameState::Replay), Some(MusicTrack::Playing));
+        assert_eq!(MusicTrack::for_state(&GameState::Victory), Some(MusicTrack::Menu));
+    }
+}
@@ -1,29 +1,110 @@
+use std::path::Path;
+use crate::audio::backend::{AudioBackend, MacroquadBackend, NullBackend, SoundEffect};
+use crate::audio::mixer::VolumeMixer;
+use crate::audio::synth::EAT_PITCH_STEPS;
+
+// งูยาวขึ้นทุก ๆ กี่ช่องถึงจะขยับเสียงกินอาหารขึ้นหนึ่งขั้น
+const EAT_PITCH_LENGTH_STEP: usize = 5;
+
 pub struct SoundManager {
-    pub sound_enabled: bool,
+    pub mixer: VolumeMixer,
+    backend: Box<dyn AudioBackend>,
+}
+
+impl Default for SoundManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SoundManager {
+    // เริ่มต้นแบบเงียบ (NullBackend)
     pub fn new() -> Self {
+        Self::with_backend(Box::new(NullBackend::default()))
+    }
+
+    pub fn with_backend(backend: Box<dyn AudioBackend>) -> Self {
         SoundManager {
-            sound_enabled: true,
+            mixer: VolumeMixer::default(),
+            backend,
         }
     }
 
-    pub fn play_eat_sound(&self) {
-        if self.sound_enabled {
-            println!("🎵 Eat sound played!");
+    // ใช้ไฟล์เสียงจาก assets ถ้ามี ที่เหลือสังเคราะห์เอง
+    // ถ้าคอมไพล์โดยไม่มี feature "audio" จะเงียบแทน
+    pub async fn load(assets_dir: &Path) -> Self {
+        if !cfg!(feature = "audio") {
+            return Self::new();
+        }
+        let backend = MacroquadBackend::load(assets_dir).await;
+        if backend.is_empty() {
+            eprintln!("Could not create any sounds, audio disabled");
+            return Self::new();
         }
+        Self::with_backend(Box::new(backend))
     }
 
-    pub fn play_crash_sound(&self) {
-        if self.sound_enabled {
-            println!("💥 Crash sound played!");
+    pub fn play(&mut self, effect: SoundEffect) {
+        self.play_variant(effect, 0);
+    }
+
+    pub fn play_eat(&mut self, snake_length: usize) {
+        let step = (snake_length / EAT_PITCH_LENGTH_STEP).min(EAT_PITCH_STEPS - 1);
+        self.play_variant(SoundEffect::Eat, step);
+    }
+
+    fn play_variant(&mut self, effect: SoundEffect, variant: usize) {
+        let volume = self.mixer.volume(effect.category());
+        if volume > 0.0 {
+            self.backend.play(effect, variant, volume);
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::PowerUpType;
+
+    fn manager() -> (SoundManager, NullBackend) {
+        let backend = NullBackend::default();
+        (SoundManager::with_backend(Box::new(backend.clone())), backend)
+    }
 
-    pub fn play_power_up_sound(&self, power_type: &str) {
-        if self.sound_enabled {
-            println!("{} activated!", power_type);
+    #[test]
+    fn records_played_effects() {
+        let (mut sounds, backend) = manager();
+        sounds.play(SoundEffect::MenuClick);
+        sounds.play(SoundEffect::PowerUp(PowerUpType::Shrink));
+        sounds.play(SoundEffect::Crash);
+        assert_eq!(
+            *backend.played.borrow(),
+            vec![
+                (SoundEffect::MenuClick, 0),
+                (SoundEffect::PowerUp(PowerUpType::Shrink), 0),
+                (SoundEffect::Crash, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn eat_pitch_rises_with_length() {
+        let (mut sounds, backend) = manager();
+        for length in [1, EAT_PITCH_LENGTH_STEP, EAT_PITCH_LENGTH_STEP * 3 + 2, 10_000] {
+            sounds.play_eat(length);
         }
+        let variants: Vec<usize> = backend.played.borrow().iter().map(|(_, variant)| *variant).collect();
+        assert_eq!(variants, vec![0, 1, 3, EAT_PITCH_STEPS - 1]);
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn skips_silent_effects() {
+        let (mut sounds, backend) = manager();
+        sounds.mixer.muted = true;
+        sounds.play(SoundEffect::Pause);
+        sounds.mixer.muted = false;
+        sounds.mixer.effects = 0.0;
+        sounds.play_eat(3);
+        assert!(backend.played.borrow().is_empty());
+    }
+}
@@ -0,0 +1,307 @@
+use crate::audio::backend::SoundEffect;
+use crate::game::PowerUpType;
+
+pub const SAMPLE_RATE: u32 = 44_100;
+pub const EAT_PITCH_STEPS: usize = 8;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Waveform {
+    Square,
+    Sawtooth,
+    Triangle,
+    Sine,
+    Noise,
+}
+
+// คำอธิบายเสียงแบบสั้น ๆ คล้าย sfxr: คลื่น, ความถี่เริ่ม/จบ และ envelope (วินาที)
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SynthParams {
+    pub waveform: Waveform,
+    pub start_frequency: f32,
+    pub end_frequency: f32,
+    pub attack: f32,
+    pub sustain: f32,
+    pub decay: f32,
+    pub duty: f32,
+    pub vibrato_depth: f32,
+    pub vibrato_speed: f32,
+    pub volume: f32,
+}
+
+impl SynthParams {
+    pub fn new(waveform: Waveform, start_frequency: f32, end_frequency: f32) -> Self {
+        SynthParams {
+            waveform,
+            start_frequency,
+            end_frequency,
+            attack: 0.0,
+            sustain: 0.05,
+            decay: 0.1,
+            duty: 0.5,
+            vibrato_depth: 0.0,
+            vibrato_speed: 0.0,
+            volume: 0.5,
+        }
+    }
+
+    pub fn envelope(mut self, attack: f32, sustain: f32, decay: f32) -> Self {
+        self.attack = attack;
+        self.sustain = sustain;
+        self.decay = decay;
+        self
+    }
+
+    pub fn vibrato(mut self, depth: f32, speed: f32) -> Self {
+        self.vibrato_depth = depth;
+        self.vibrato_speed = speed;
+        self
+    }
+
+    pub fn volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.attack + self.sustain + self.decay
+    }
+
+    // เสียงกินอาหารสูงขึ้นทีละเสียงเต็มตามความยาวงู
+    pub fn eat(pitch_step: usize) -> Self {
+        let base = 523.25 * 2f32.powf(pitch_step as f32 * 2.0 / 12.0);
+        SynthParams::new(Waveform::Square, base, base * 1.5).envelope(0.0, 0.03, 0.07)
+    }
+
+    pub fn for_effect(effect: SoundEffect) -> Self {
+        match effect {
+            SoundEffect::Eat => Self::eat(0),
+            SoundEffect::Crash => SynthParams::new(Waveform::Noise, 900.0, 60.0)
+                .envelope(0.0, 0.08, 0.45)
+                .volume(0.6),
+            SoundEffect::PowerUp(PowerUpType::SpeedBoost) => {
+                SynthParams::new(Waveform::Square, 300.0, 1200.0).envelope(0.0, 0.15, 0.1)
+            }
+            SoundEffect::PowerUp(PowerUpType::Shrink) => {
+                SynthParams::new(Waveform::Triangle, 900.0, 250.0).envelope(0.0, 0.12, 0.1)
+            }
+            SoundEffect::PowerUp(PowerUpType::GhostMode) => SynthParams::new(Waveform::Sine, 300.0, 700.0)
+                .envelope(0.05, 0.2, 0.2)
+                .vibrato(0.08, 9.0),
+            SoundEffect::PowerUpExpired(power_type) => {
+                let pickup = Self::for_effect(SoundEffect::PowerUp(power_type));
+                SynthParams::new(pickup.waveform, pickup.end_frequency, pickup.start_frequency)
+                    .envelope(0.0, 0.1, 0.15)
+                    .volume(0.3)
+            }
+            SoundEffect::MenuClick => SynthParams::new(Waveform::Square, 1000.0, 1000.0)
+                .envelope(0.0, 0.02, 0.02)
+                .volume(0.3),
+            SoundEffect::Pause => SynthParams::new(Waveform::Sine, 660.0, 440.0)
+                .envelope(0.0, 0.06, 0.08)
+                .volume(0.4),
+            // เสียงชนะยาวกว่าเสียงอื่น ไต่ขึ้นสองออกเทฟพร้อม vibrato
+            SoundEffect::Victory => SynthParams::new(Waveform::Square, 523.25, 2093.0)
+                .envelope(0.02, 0.45, 0.4)
+                .vibrato(0.04, 10.0)
+                .volume(0.45),
+            SoundEffect::LevelComplete => SynthParams::new(Waveform::Triangle, 440.0, 880.0)
+                .envelope(0.01, 0.25, 0.3)
+                .vibrato(0.02, 6.0),
+        }
+    }
+}
+
+// สร้างตัวอย่างเสียง (ค่าอยู่ในช่วง -1.0..=1.0) แบบ deterministic
+pub fn synthesize(params: &SynthParams) -> Vec<f32> {
+    let sample_count = (params.duration() * SAMPLE_RATE as f32).round() as usize;
+    let mut samples = Vec::with_capacity(sample_count);
+
+    let mut phase = 0.0f32;
+    let mut noise_value = 0.0f32;
+    let mut noise_state: u32 = 0x1234_5678;
+
+    for i in 0..sample_count {
+        let t = i as f32 / SAMPLE_RATE as f32;
+        let progress = i as f32 / sample_count.max(1) as f32;
+
+        let mut frequency = params.start_frequency + (params.end_frequency - params.start_frequency) * progress;
+        if params.vibrato_depth > 0.0 {
+            frequency *= 1.0 + params.vibrato_depth * (t * params.vibrato_speed * std::f32::consts::TAU).sin();
+        }
+
+        let previous_phase = phase;
+        phase = (phase + frequency / SAMPLE_RATE as f32).fract();
+
+        let value = match params.waveform {
+            Waveform::Square => {
+                if phase < params.duty { 1.0 } else { -1.0 }
+            }
+            Waveform::Sawtooth => 2.0 * phase - 1.0,
+            Waveform::Triangle => 1.0 - 4.0 * (phase - 0.5).abs(),
+            Waveform::Sine => (phase * std::f32::consts::TAU).sin(),
+            Waveform::Noise => {
+                // สุ่มค่าใหม่ทุกครั้งที่ phase วนรอบ ความถี่จึงยังมีผลกับเสียง noise
+                if phase < previous_phase || i == 0 {
+                    noise_state ^= noise_state << 13;
+                    noise_state ^= noise_state >> 17;
+                    noise_state ^= noise_state << 5;
+                    noise_value = noise_state as f32 / u32::MAX as f32 * 2.0 - 1.0;
+                }
+                noise_value
+            }
+        };
+
+        samples.push((value * envelope(params, t) * params.volume).clamp(-1.0, 1.0));
+    }
+    samples
+}
+
+fn envelope(params: &SynthParams, t: f32) -> f32 {
+    if t < params.attack {
+        t / params.attack
+    } else if t < params.attack + params.sustain {
+        1.0
+    } else if params.decay > 0.0 {
+        (1.0 - (t - params.attack - params.sustain) / params.decay).max(0.0)
+    } else {
+        0.0
+    }
+}
+
+// โน้ตหนึ่งตัวในลูปเพลง: เลขโน้ตแบบ MIDI, จังหวะที่เริ่ม และความยาว (หน่วยเป็น beat)
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Note {
+    pub pitch: u8,
+    pub start: f32,
+    pub length: f32,
+}
+
+pub fn midi_to_frequency(pitch: u8) -> f32 {
+    440.0 * 2f32.powf((pitch as f32 - 69.0) / 12.0)
+}
+
+// เรนเดอร์โน้ตทั้งหมดลงบัฟเฟอร์ยาว beats จังหวะ โดยใช้ instrument เป็นต้นแบบเสียง
+pub fn render_notes(notes: &[Note], instrument: &SynthParams, bpm: f32, beats: f32) -> Vec<f32> {
+    let seconds_per_beat = 60.0 / bpm;
+    let total = (beats * seconds_per_beat * SAMPLE_RATE as f32).round() as usize;
+    let mut buffer = vec![0.0f32; total];
+
+    for note in notes {
+        let frequency = midi_to_frequency(note.pitch);
+        let ratio = instrument.end_frequency / instrument.start_frequency;
+        let mut params = *instrument;
+        params.start_frequency = frequency;
+        params.end_frequency = frequency * ratio;
+        params.sustain = (note.length * seconds_per_beat - params.attack - params.decay).max(0.0);
+
+        let offset = (note.start * seconds_per_beat * SAMPLE_RATE as f32).round() as usize;
+        for (i, sample) in synthesize(&params).into_iter().enumerate() {
+            // โน้ตที่ยาวเกินลูปจะวนกลับไปต้นบัฟเฟอร์ ลูปจึงต่อกันได้เนียน
+            buffer[(offset + i) % total] += sample;
+        }
+    }
+
+    for sample in &mut buffer {
+        *sample = sample.clamp(-1.0, 1.0);
+    }
+    buffer
+}
+
+// ห่อ samples เป็นไฟล์ WAV (PCM 16 bit mono) ในหน่วยความจำ
+pub fn encode_wav(samples: &[f32]) -> Vec<u8> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut bytes = Vec::with_capacity(44 + data_len as usize);
+
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVE");
+    bytes.extend_from_slice(b"fmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    bytes.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+
+    for sample in samples {
+        let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_params() -> Vec<SynthParams> {
+        let mut params: Vec<SynthParams> = SoundEffect::ALL.iter().map(|effect| SynthParams::for_effect(*effect)).collect();
+        params.push(SynthParams::new(Waveform::Sawtooth, 200.0, 400.0).envelope(0.01, 0.02, 0.03));
+        params
+    }
+
+    #[test]
+    fn length_matches_duration() {
+        for params in all_params() {
+            let expected = (params.duration() * SAMPLE_RATE as f32).round() as usize;
+            assert_eq!(synthesize(&params).len(), expected, "{:?}", params);
+        }
+        assert!(synthesize(&SynthParams::new(Waveform::Sine, 440.0, 440.0).envelope(0.0, 0.0, 0.0)).is_empty());
+    }
+
+    #[test]
+    fn samples_stay_in_range_and_are_not_silent() {
+        for params in all_params() {
+            let samples = synthesize(&params);
+            assert!(samples.iter().all(|s| (-1.0..=1.0).contains(s)), "{:?}", params);
+            let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+            assert!(peak > 0.1 && peak <= params.volume + f32::EPSILON, "{:?} peaks at {}", params, peak);
+        }
+    }
+
+    #[test]
+    fn is_deterministic() {
+        for params in all_params() {
+            assert_eq!(synthesize(&params), synthesize(&params));
+        }
+    }
+
+    #[test]
+    fn wav_header_describes_16_bit_mono_pcm() {
+        let samples = [0.0, 1.0, -1.0, 2.0];
+        let wav = encode_wav(&samples);
+        let u16_at = |i: usize| u16::from_le_bytes([wav[i], wav[i + 1]]);
+        let u32_at = |i: usize| u32::from_le_bytes([wav[i], wav[i + 1], wav[i + 2], wav[i + 3]]);
+
+        assert_eq!(wav.len(), 44 + samples.len() * 2);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(u32_at(4) as usize, wav.len() - 8);
+        assert_eq!(&wav[8..16], b"WAVEfmt ");
+        assert_eq!(u32_at(16), 16);
+        assert_eq!(u16_at(20), 1);
+        assert_eq!(u16_at(22), 1);
+        assert_eq!(u32_at(24), SAMPLE_RATE);
+        assert_eq!(u32_at(28), SAMPLE_RATE * 2);
+        assert_eq!(u16_at(32), 2);
+        assert_eq!(u16_at(34), 16);
+        assert_eq!(&wav[36..40], b"data");
+        assert_eq!(u32_at(40) as usize, samples.len() * 2);
+
+        let pcm: Vec<i16> = wav[44..].chunks(2).map(|pair| i16::from_le_bytes([pair[0], pair[1]])).collect();
+        assert_eq!(pcm, vec![0, i16::MAX, -i16::MAX, i16::MAX]);
+    }
+
+    // ทุก effect มีเสียงและชื่อไฟล์ของตัวเอง ไม่ใช้เสียงซ้ำกับ effect อื่น
+    #[test]
+    fn every_effect_has_its_own_sound() {
+        for (i, a) in SoundEffect::ALL.iter().enumerate() {
+            for b in &SoundEffect::ALL[i + 1..] {
+                assert_ne!(SynthParams::for_effect(*a), SynthParams::for_effect(*b), "{:?} {:?}", a, b);
+                assert_ne!(a.file_name(), b.file_name());
+            }
+        }
+    }
+}
@@ -0,0 +1,333 @@
+// รันหลายเกมแบบไม่เปิดหน้าต่างด้วย bot แล้วสรุปสถิติ ใช้เทียบ bot และปรับกติกา
+// เช่นโอกาสเกิด Power-up และจำนวนอาหารที่งูเร็วขึ้น
+//
+//   cargo run --release --bin snake-sim -- [--games N] [--seed N] [--controller autopilot|greedy|solver]
+//       [--mode normal|obstacle|maze|campaign] [--board WxH|small|medium|large] [--boundary wrap|solid|bounce]
+//       [--level FILE] [--max-ticks N] [--power-up-chance F] [--speed-thresholds 10,20,40] [--format csv|json]
+//
+// แบบ csv พิมพ์ผลรายเกมออก stdout และสรุปออก stderr แบบ json พิมพ์ทั้งสองอย่างเป็นเอกสารเดียว
+
+use snake_gui::ai::{Autopilot, HamiltonianSolver};
+use snake_gui::controller::{self, Controller};
+use snake_gui::game::{
+    parse_speed_thresholds, Board, BoardPreset, BoundaryRule, DeathCause, GameConfig, GameMode, Level, Simulation,
+    SimulationState,
+};
+use snake_gui::utils::{arg_value, check_flags, parse_arg};
+use std::ops::Range;
+use std::path::Path;
+use std::process::ExitCode;
+use std::sync::Arc;
+
+const CONTROLLERS: [&str; 3] = ["autopilot", "greedy", "solver"];
+const FLAGS: [&str; 11] = [
+    "--games",
+    "--seed",
+    "--controller",
+    "--mode",
+    "--board",
+    "--boundary",
+    "--level",
+    "--max-ticks",
+    "--power-up-chance",
+    "--speed-thresholds",
+    "--format",
+];
+
+struct Options {
+    seeds: Range<u64>,
+    controller: String,
+    config: GameConfig,
+    max_ticks: u64,
+    json: bool,
+}
+
+fn parse_options() -> Result<Options, String> {
+    check_flags(&FLAGS)?;
+    let controller = arg_value("--controller").unwrap_or_else(|| "autopilot".to_string());
+    if !CONTROLLERS.contains(&controller.as_str()) {
+        return Err(format!("unknown controller '{}' (expected {})", controller, CONTROLLERS.join(", ")));
+    }
+    let json = match arg_value("--format").as_deref() {
+        None | Some("csv") => false,
+        Some("json") => true,
+        Some(other) => return Err(format!("unknown format '{}' (expected csv or json)", other)),
+    };
+
+    let mut config = match arg_value("--level") {
+        Some(path) => {
+            let level = Level::load(Path::new(&path)).map_err(|e| format!("could not load level {}: {}", path, e))?;
+            GameConfig::for_level(Arc::new(level))
+        }
+        None => {
+            let mode = match arg_value("--mode") {
+                Some(name) => GameMode::from_name(&name).ok_or_else(|| format!("unknown mode '{}'", name))?,
+                None => GameMode::Normal,
+            };
+            if mode == GameMode::Campaign {
+                return Err("campaign mode needs --level".to_string());
+            }
+            let board = match arg_value("--board") {
+                Some(text) => BoardPreset::from_name(&text)
+                    .filter(|preset| *preset != BoardPreset::Custom)
+                    .map(|preset| Board::from_preset(preset, Board::default()))
+                    .or_else(|| Board::from_text(&text))
+                    .ok_or_else(|| format!("invalid board '{}'", text))?,
+                None => Board::default(),
+            };
+            let boundary = match arg_value("--boundary") {
+                Some(name) => BoundaryRule::from_name(&name).ok_or_else(|| format!("unknown boundary '{}'", name))?,
+                None => BoundaryRule::Wrap,
+            };
+            GameConfig::new(mode, board, boundary)
+        }
+    };
+    if let Some(text) = arg_value("--power-up-chance") {
+        let chance: f32 = text.parse().map_err(|_| format!("invalid value for --power-up-chance: '{}'", text))?;
+        if !(0.0..=1.0).contains(&chance) {
+            return Err("--power-up-chance must be between 0 and 1".to_string());
+        }
+        config.power_up_chance = Some(chance);
+    }
+    if let Some(text) = arg_value("--speed-thresholds") {
+        config.speed_thresholds =
+            parse_speed_thresholds(&text).ok_or_else(|| format!("invalid value for --speed-thresholds: '{}'", text))?;
+    }
+
+    let games = parse_arg("--games", 100)?;
+    let first_seed: u64 = parse_arg("--seed", 0)?;
+    let last_seed = first_seed
+        .checked_add(games)
+        .ok_or_else(|| format!("--seed {} with --games {} runs past the largest seed", first_seed, games))?;
+
+    Ok(Options {
+        seeds: first_seed..last_seed,
+        controller,
+        config,
+        max_ticks: parse_arg("--max-ticks", 1_000_000)?,
+        json,
+    })
+}
+
+fn new_controller(name: &str) -> Box<dyn Controller> {
+    match name {
+        "greedy" => Box::new(Autopilot::greedy()),
+        "solver" => Box::new(HamiltonianSolver::new()),
+        _ => Box::new(Autopilot::new()),
+    }
+}
+
+// ผลของหนึ่งเกม
+struct GameStats {
+    seed: u64,
+    result: &'static str,
+    death_cause: Option<&'static str>,
+    score: usize,
+    ticks: u64,
+    food_eaten: u32,
+    power_ups_collected: u32,
+}
+
+impl GameStats {
+    fn from_simulation(sim: &Simulation) -> Self {
+        GameStats {
+            seed: sim.seed,
+            result: match sim.state {
+                SimulationState::Running => "timeout",
+                SimulationState::GameOver => "died",
+                SimulationState::LevelComplete => "level_complete",
+                SimulationState::Won => "won",
+            },
+            death_cause: sim.death_cause.map(|cause| match cause {
+                DeathCause::SelfCollision => "self",
+                DeathCause::Obstacle => "obstacle",
+                DeathCause::Wall => "wall",
+            }),
+            score: sim.score(),
+            ticks: sim.tick,
+            food_eaten: sim.food_eaten,
+            power_ups_collected: sim.power_ups_collected,
+        }
+    }
+
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"seed\": {}, \"result\": \"{}\", \"death_cause\": {}, \"score\": {}, \"ticks\": {}, \"food_eaten\": {}, \"power_ups_collected\": {}}}",
+            self.seed,
+            self.result,
+            self.death_cause.map_or_else(|| "null".to_string(), |cause| format!("\"{}\"", cause)),
+            self.score,
+            self.ticks,
+            self.food_eaten,
+            self.power_ups_collected
+        )
+    }
+}
+
+const CSV_HEADER: &str = "seed,result,death_cause,score,ticks,food_eaten,power_ups_collected";
+
+fn csv_row(stats: &GameStats) -> String {
+    format!(
+        "{},{},{},{},{},{},{}",
+        stats.seed,
+        stats.result,
+        stats.death_cause.unwrap_or(""),
+        stats.score,
+        stats.ticks,
+        stats.food_eaten,
+        stats.power_ups_collected
+    )
+}
+
+// ค่าสรุปของตัวเลขหนึ่งคอลัมน์ percentile แบบ nearest-rank
+struct Distribution {
+    mean: f64,
+    min: u64,
+    p10: u64,
+    p50: u64,
+    p90: u64,
+    max: u64,
+}
+
+impl Distribution {
+    fn new(mut values: Vec<u64>) -> Self {
+        values.sort_unstable();
+        let percentile = |p: f64| -> u64 {
+            if values.is_empty() {
+                return 0;
+            }
+            let rank = ((p / 100.0) * values.len() as f64).ceil() as usize;
+            values[rank.clamp(1, values.len()) - 1]
+        };
+        Distribution {
+            mean: if values.is_empty() { 0.0 } else { values.iter().sum::<u64>() as f64 / values.len() as f64 },
+            min: values.first().copied().unwrap_or(0),
+            p10: percentile(10.0),
+            p50: percentile(50.0),
+            p90: percentile(90.0),
+            max: values.last().copied().unwrap_or(0),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"mean\": {:.2}, \"min\": {}, \"p10\": {}, \"p50\": {}, \"p90\": {}, \"max\": {}}}",
+            self.mean, self.min, self.p10, self.p50, self.p90, self.max
+        )
+    }
+
+    fn to_text(&self) -> String {
+        format!(
+            "mean {:.2}  min {}  p10 {}  p50 {}  p90 {}  max {}",
+            self.mean, self.min, self.p10, self.p50, self.p90, self.max
+        )
+    }
+}
+
+// นับจำนวนเกมตามค่าในคอลัมน์ เรียงตามที่เจอครั้งแรก
+fn count_by(values: impl Iterator<Item = &'static str>) -> Vec<(&'static str, usize)> {
+    let mut counts: Vec<(&'static str, usize)> = Vec::new();
+    for value in values {
+        match counts.iter_mut().find(|(name, _)| *name == value) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((value, 1)),
+        }
+    }
+    counts
+}
+
+struct Summary {
+    games: usize,
+    results: Vec<(&'static str, usize)>,
+    death_causes: Vec<(&'static str, usize)>,
+    score: Distribution,
+    ticks: Distribution,
+    power_ups: Distribution,
+}
+
+impl Summary {
+    fn new(stats: &[GameStats]) -> Self {
+        Summary {
+            games: stats.len(),
+            results: count_by(stats.iter().map(|game| game.result)),
+            death_causes: count_by(stats.iter().filter_map(|game| game.death_cause)),
+            score: Distribution::new(stats.iter().map(|game| game.score as u64).collect()),
+            ticks: Distribution::new(stats.iter().map(|game| game.ticks).collect()),
+            power_ups: Distribution::new(stats.iter().map(|game| game.power_ups_collected as u64).collect()),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        let counts = |counts: &[(&str, usize)]| {
+            let fields: Vec<String> = counts.iter().map(|(name, count)| format!("\"{}\": {}", name, count)).collect();
+            format!("{{{}}}", fields.join(", "))
+        };
+        format!(
+            "{{\"games\": {}, \"results\": {}, \"death_causes\": {}, \"score\": {}, \"ticks\": {}, \"power_ups_collected\": {}}}",
+            self.games,
+            counts(&self.results),
+            counts(&self.death_causes),
+            self.score.to_json(),
+            self.ticks.to_json(),
+            self.power_ups.to_json()
+        )
+    }
+
+    fn to_text(&self) -> String {
+        let counts = |counts: &[(&str, usize)]| {
+            let fields: Vec<String> = counts.iter().map(|(name, count)| format!("{} {}", name, count)).collect();
+            if fields.is_empty() { "-".to_string() } else { fields.join(", ") }
+        };
+        format!(
+            "games: {}\nresults: {}\ndeath causes: {}\nscore: {}\nticks: {}\npower-ups collected: {}",
+            self.games,
+            counts(&self.results),
+            counts(&self.death_causes),
+            self.score.to_text(),
+            self.ticks.to_text(),
+            self.power_ups.to_text()
+        )
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match parse_options() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    if !options.json {
+        println!("{}", CSV_HEADER);
+    }
+    let mut stats = Vec::new();
+    for seed in options.seeds.clone() {
+        let mut controller = new_controller(&options.controller);
+        let sim = controller::play(controller.as_mut(), options.config.clone(), seed, options.max_ticks);
+        let game = GameStats::from_simulation(&sim);
+        // csv พิมพ์ทีละเกมเพื่อดูความคืบหน้าระหว่างรันนาน ๆ ได้
+        if !options.json {
+            println!("{}", csv_row(&game));
+        }
+        stats.push(game);
+    }
+
+    let summary = Summary::new(&stats);
+    if options.json {
+        let games: Vec<String> = stats.iter().map(|game| format!("    {}", game.to_json())).collect();
+        println!(
+            "{{\n  \"controller\": \"{}\",\n  \"mode\": \"{}\",\n  \"board\": \"{}\",\n  \"boundary\": \"{}\",\n  \"games\": [\n{}\n  ],\n  \"summary\": {}\n}}",
+            options.controller,
+            options.config.game_mode.name(),
+            options.config.board.to_text(),
+            options.config.boundary.name(),
+            games.join(",\n"),
+            summary.to_json()
+        );
+    } else {
+        eprintln!("{}", summary.to_text());
+    }
+    ExitCode::SUCCESS
+}
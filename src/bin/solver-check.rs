@@ -0,0 +1,110 @@
+// ให้ HamiltonianSolver เล่นจนเต็มกระดานบนหลายขนาดและหลาย seed
+// ใช้ตรวจว่าเงื่อนไขชนะตอนกระดานเต็มยังทำงานถูก ออกด้วย code 1 ถ้ามีเกมไหนไม่ชนะ
+//
+//   cargo run --release --bin solver-check -- [--seeds N] [--max-ticks N]
+
+use snake_gui::ai::{run_solver, HamiltonianSolver};
+use snake_gui::game::{Board, BoardPreset, BoundaryRule, GameConfig, GameMode, Level, Simulation, SimulationState};
+use snake_gui::utils::parse_arg;
+use std::process::ExitCode;
+use std::sync::Arc;
+
+// ด่านที่สิ่งกีดขวางวางตรงบล็อก 2x2 พอดี ใช้ตรวจการสร้างวงรอบบนกระดานที่มีสิ่งกีดขวาง
+const BLOCK_LEVEL: &str = "\
+name: Block pillars
+direction: right
+boundary: solid
+---
+................
+................
+....##....##....
+....##....##....
+................
+........S.......
+................
+................
+....##....##....
+....##....##....
+................
+................
+";
+
+// ตรวจผลหนึ่งเกม คืนค่าข้อความอธิบายถ้าผิด
+fn check(sim: &Simulation) -> Result<(), String> {
+    if sim.state != SimulationState::Won {
+        return Err(format!("ended as {:?} (cause {:?})", sim.state, sim.death_cause));
+    }
+    if sim.occupancy.free_count() != 0 {
+        return Err(format!("won with {} free cells left", sim.occupancy.free_count()));
+    }
+    let playable = sim.config.board.cell_count() - sim.obstacles.len();
+    if sim.snake.len() != playable {
+        return Err(format!("snake length {} but {} playable cells", sim.snake.len(), playable));
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let (seeds, max_ticks) = match (parse_arg("--seeds", 3), parse_arg("--max-ticks", 50_000_000)) {
+        (Ok(seeds), Ok(max_ticks)) => (seeds, max_ticks),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("{}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    let level = match Level::parse("block_pillars", BLOCK_LEVEL) {
+        Ok(level) => Arc::new(level),
+        Err(e) => {
+            eprintln!("built-in level is invalid: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let large = Board::from_preset(BoardPreset::Large, Board::default());
+    let cases = [
+        GameConfig::new(GameMode::Normal, Board::new(40, 30), BoundaryRule::Solid),
+        GameConfig::new(GameMode::Normal, large, BoundaryRule::Wrap),
+        GameConfig::new(GameMode::Normal, Board::new(21, 15), BoundaryRule::Wrap),
+        GameConfig::new(GameMode::Normal, Board::new(15, 20), BoundaryRule::Bounce),
+        GameConfig::new(GameMode::Normal, Board::new(8, 8), BoundaryRule::Solid),
+        GameConfig::for_level(level),
+    ];
+
+    let mut failures = 0;
+    for config in cases {
+        let (board, boundary) = (config.board, config.boundary);
+        let probe = Simulation::new(config.clone(), 0);
+        let mut solver = HamiltonianSolver::new();
+        solver.prepare(&probe);
+        if solver.cycle().is_none() {
+            println!("{} {}: no Hamiltonian cycle", board.to_text(), boundary.name());
+            failures += 1;
+            continue;
+        }
+        for seed in 0..seeds {
+            let sim = run_solver(config.clone(), seed, max_ticks);
+            let result = check(&sim);
+            println!(
+                "{} {} seed {}: {} length {} ticks {}",
+                board.to_text(),
+                boundary.name(),
+                seed,
+                match &result {
+                    Ok(()) => "won".to_string(),
+                    Err(e) => format!("FAILED {}", e),
+                },
+                sim.snake.len(),
+                sim.tick
+            );
+            if result.is_err() {
+                failures += 1;
+            }
+        }
+    }
+
+    if failures > 0 {
+        eprintln!("{} check(s) failed", failures);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
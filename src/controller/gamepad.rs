@@ -0,0 +1,174 @@
+use crate::controller::Controller;
+use crate::game::game_state::Direction;
+use crate::game::simulation::Simulation;
+
+// ค่า analog stick ที่น้อยกว่านี้ถือว่าไม่ได้โยก
+pub const STICK_DEADZONE: f32 = 0.5;
+
+// อุปกรณ์จอย บอกแค่ว่าตอนนี้กดทิศไหนค้างอยู่
+pub trait GamepadSource {
+    fn held_direction(&mut self) -> Option<Direction>;
+}
+
+// แปลงค่า stick (x ขวาเป็นบวก, y ขึ้นเป็นบวก) เป็นทิศตามแกนที่โยกมากกว่า
+pub fn stick_direction(x: f32, y: f32) -> Option<Direction> {
+    if x.abs() < STICK_DEADZONE && y.abs() < STICK_DEADZONE {
+        None
+    } else if x.abs() >= y.abs() {
+        Some(if x > 0.0 { Direction::Right } else { Direction::Left })
+    } else {
+        Some(if y > 0.0 { Direction::Up } else { Direction::Down })
+    }
+}
+
+// จอยเกม นับเป็นการกดเฉพาะตอนทิศที่ค้างเปลี่ยน เหมือนกดปุ่มคีย์บอร์ดทีละครั้ง
+pub struct GamepadController<S: GamepadSource> {
+    source: S,
+    held: Option<Direction>,
+    pending: Option<Direction>,
+}
+
+impl<S: GamepadSource> GamepadController<S> {
+    pub fn new(source: S) -> Self {
+        GamepadController {
+            source,
+            held: None,
+            pending: None,
+        }
+    }
+}
+
+impl<S: GamepadSource> Controller for GamepadController<S> {
+    fn poll(&mut self) {
+        let held = self.source.held_direction();
+        if held.is_some() && held != self.held {
+            self.pending = held;
+        }
+        self.held = held;
+    }
+
+    fn next_direction(&mut self, _sim: &Simulation) -> Option<Direction> {
+        self.pending.take()
+    }
+
+    fn reset(&mut self) {
+        self.pending = None;
+    }
+
+    fn is_human(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &'static str {
+        "GAMEPAD"
+    }
+}
+
+// จอยจริงผ่าน gilrs อ่านจากทุกจอยที่เสียบอยู่
+#[cfg(feature = "gamepad")]
+pub struct GilrsSource {
+    gilrs: gilrs::Gilrs,
+}
+
+#[cfg(feature = "gamepad")]
+impl GilrsSource {
+    pub fn new() -> Result<Self, String> {
+        gilrs::Gilrs::new()
+            .map(|gilrs| GilrsSource { gilrs })
+            .map_err(|e| format!("could not open gamepads: {}", e))
+    }
+}
+
+#[cfg(feature = "gamepad")]
+impl GamepadSource for GilrsSource {
+    fn held_direction(&mut self) -> Option<Direction> {
+        use gilrs::{Axis, Button};
+
+        // ต้องดึง event ออกให้หมด gilrs ถึงจะอัปเดตสถานะปุ่ม
+        while self.gilrs.next_event().is_some() {}
+
+        for (_, gamepad) in self.gilrs.gamepads() {
+            let dpad = [
+                (Button::DPadUp, Direction::Up),
+                (Button::DPadDown, Direction::Down),
+                (Button::DPadLeft, Direction::Left),
+                (Button::DPadRight, Direction::Right),
+            ];
+            for (button, direction) in dpad {
+                if gamepad.is_pressed(button) {
+                    return Some(direction);
+                }
+            }
+            let stick = stick_direction(gamepad.value(Axis::LeftStickX), gamepad.value(Axis::LeftStickY));
+            if stick.is_some() {
+                return stick;
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::board::Board;
+    use crate::game::config::{BoundaryRule, GameConfig};
+    use crate::game::game_state::GameMode;
+
+    // จอยปลอมที่คืนทิศที่ค้างตามลำดับที่กำหนด ทีละ poll
+    struct ScriptedSource {
+        held: Vec<Option<Direction>>,
+        next: usize,
+    }
+
+    impl GamepadSource for ScriptedSource {
+        fn held_direction(&mut self) -> Option<Direction> {
+            let held = self.held.get(self.next).copied().flatten();
+            self.next += 1;
+            held
+        }
+    }
+
+    #[test]
+    fn stick_uses_deadzone_and_larger_axis() {
+        assert_eq!(stick_direction(0.0, 0.0), None);
+        assert_eq!(stick_direction(0.49, -0.49), None);
+        assert_eq!(stick_direction(STICK_DEADZONE, 0.0), Some(Direction::Right));
+        assert_eq!(stick_direction(-0.8, 0.2), Some(Direction::Left));
+        assert_eq!(stick_direction(0.3, 0.9), Some(Direction::Up));
+        assert_eq!(stick_direction(0.1, -0.6), Some(Direction::Down));
+        // โยกทแยงเท่ากันใช้แกนนอน
+        assert_eq!(stick_direction(-0.7, 0.7), Some(Direction::Left));
+    }
+
+    #[test]
+    fn fires_once_per_press() {
+        let sim = Simulation::new(GameConfig::new(GameMode::Normal, Board::SMALL, BoundaryRule::Wrap), 0);
+        let held = [
+            None,
+            Some(Direction::Up),
+            Some(Direction::Up),
+            Some(Direction::Up),
+            Some(Direction::Left),
+            None,
+            Some(Direction::Left),
+        ];
+        let mut controller = GamepadController::new(ScriptedSource { held: held.to_vec(), next: 0 });
+        let mut fired = Vec::new();
+        for _ in 0..held.len() {
+            controller.poll();
+            fired.push(controller.next_direction(&sim));
+        }
+        assert_eq!(
+            fired,
+            [None, Some(Direction::Up), None, None, Some(Direction::Left), None, Some(Direction::Left)]
+        );
+
+        // กดแล้วเริ่มเกมใหม่ก่อนงูได้ใช้ ปุ่มนั้นถูกทิ้ง
+        let mut controller = GamepadController::new(ScriptedSource { held: vec![Some(Direction::Down)], next: 0 });
+        controller.poll();
+        controller.reset();
+        assert_eq!(controller.next_direction(&sim), None);
+        assert!(controller.is_human());
+    }
+}
@@ -0,0 +1,51 @@
+use macroquad::prelude::*;
+use crate::controller::Controller;
+use crate::game::game_state::Direction;
+use crate::game::simulation::Simulation;
+
+// ลูกศรบนคีย์บอร์ด เก็บปุ่มล่าสุดไว้จนกว่างูจะได้ใช้
+pub struct KeyboardController {
+    pending: Option<Direction>,
+}
+
+impl Default for KeyboardController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyboardController {
+    pub fn new() -> Self {
+        KeyboardController { pending: None }
+    }
+}
+
+impl Controller for KeyboardController {
+    fn poll(&mut self) {
+        if is_key_pressed(KeyCode::Up) {
+            self.pending = Some(Direction::Up);
+        } else if is_key_pressed(KeyCode::Down) {
+            self.pending = Some(Direction::Down);
+        } else if is_key_pressed(KeyCode::Left) {
+            self.pending = Some(Direction::Left);
+        } else if is_key_pressed(KeyCode::Right) {
+            self.pending = Some(Direction::Right);
+        }
+    }
+
+    fn next_direction(&mut self, _sim: &Simulation) -> Option<Direction> {
+        self.pending.take()
+    }
+
+    fn reset(&mut self) {
+        self.pending = None;
+    }
+
+    fn is_human(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &'static str {
+        "KEYBOARD"
+    }
+}
@@ -0,0 +1,160 @@
+pub mod gamepad;
+pub mod keyboard;
+pub mod replay;
+
+pub use gamepad::{GamepadController, GamepadSource};
+#[cfg(feature = "gamepad")]
+pub use gamepad::GilrsSource;
+pub use keyboard::KeyboardController;
+
+use crate::game::config::GameConfig;
+use crate::game::game_state::Direction;
+use crate::game::simulation::{GameEvent, Simulation};
+
+// แหล่งที่มาของทิศทางงู: คีย์บอร์ด จอย ไฟล์ replay หรือ AI
+pub trait Controller {
+    // เรียกเฟรมละครั้งก่อนเดิน tick ใช้อ่านอุปกรณ์แล้วเก็บปุ่มที่กดไว้
+    fn poll(&mut self) {}
+
+    // ทิศที่ส่งให้ Simulation::step ใน tick นี้ (None = ไปทางเดิม)
+    // เห็นสถานะเกมแบบอ่านอย่างเดียว
+    fn next_direction(&mut self, sim: &Simulation) -> Option<Direction>;
+
+    // ทิ้งปุ่มที่ค้างไว้เมื่อเริ่มเกมใหม่
+    fn reset(&mut self) {}
+
+    // ผู้เล่นจริงถึงจะได้ลงตารางคะแนนและบันทึกความคืบหน้า
+    fn is_human(&self) -> bool {
+        false
+    }
+
+    fn name(&self) -> &'static str;
+}
+
+// เดินหนึ่ง tick โดยให้ controller เลือกทิศ
+pub fn step<C: Controller + ?Sized>(controller: &mut C, sim: &mut Simulation) -> Vec<GameEvent> {
+    let input = controller.next_direction(sim);
+    sim.step(input)
+}
+
+// สำหรับ AI ที่คิดทีละการขยับ เรียก plan เฉพาะ tick ที่งูจะขยับ tick อื่นไปทางเดิม
+pub fn plan_on_move(sim: &Simulation, plan: impl FnOnce(&Simulation) -> Option<Direction>) -> Option<Direction> {
+    if sim.moves_next_step() { plan(sim) } else { None }
+}
+
+// เล่นหนึ่งเกมแบบไม่เปิดหน้าต่างจนจบหรือครบ max_ticks
+pub fn play<C: Controller + ?Sized>(controller: &mut C, config: GameConfig, seed: u64, max_ticks: u64) -> Simulation {
+    let mut sim = Simulation::new(config, seed);
+    while !sim.is_over() && sim.tick < max_ticks {
+        step(controller, &mut sim);
+    }
+    sim
+}
+
+// รวมหลาย controller เข้าด้วยกัน เช่นคีย์บอร์ดกับจอย ใช้ทิศจากตัวแรกที่มี
+pub struct MultiController {
+    pub controllers: Vec<Box<dyn Controller>>,
+}
+
+impl MultiController {
+    pub fn new(controllers: Vec<Box<dyn Controller>>) -> Self {
+        MultiController { controllers }
+    }
+}
+
+impl Controller for MultiController {
+    fn poll(&mut self) {
+        for controller in &mut self.controllers {
+            controller.poll();
+        }
+    }
+
+    fn next_direction(&mut self, sim: &Simulation) -> Option<Direction> {
+        // ถามทุกตัวเพื่อไม่ให้ปุ่มที่กดค้างในตัวอื่นหลุดไปใช้ทีหลัง
+        let mut direction = None;
+        for controller in &mut self.controllers {
+            let next = controller.next_direction(sim);
+            direction = direction.or(next);
+        }
+        direction
+    }
+
+    fn reset(&mut self) {
+        for controller in &mut self.controllers {
+            controller.reset();
+        }
+    }
+
+    fn is_human(&self) -> bool {
+        self.controllers.iter().any(|controller| controller.is_human())
+    }
+
+    fn name(&self) -> &'static str {
+        self.controllers.first().map_or("NONE", |controller| controller.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::board::Board;
+    use crate::game::config::BoundaryRule;
+    use crate::game::game_state::GameMode;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    // controller ปลอมที่คืนทิศตามลำดับ และนับว่าถูกเรียกกี่ครั้ง
+    struct Scripted {
+        directions: Vec<Option<Direction>>,
+        asked: Rc<Cell<usize>>,
+        human: bool,
+    }
+
+    impl Scripted {
+        fn boxed(directions: &[Option<Direction>], human: bool) -> (Box<dyn Controller>, Rc<Cell<usize>>) {
+            let asked = Rc::new(Cell::new(0));
+            let controller = Scripted { directions: directions.to_vec(), asked: asked.clone(), human };
+            (Box::new(controller), asked)
+        }
+    }
+
+    impl Controller for Scripted {
+        fn next_direction(&mut self, _sim: &Simulation) -> Option<Direction> {
+            let direction = self.directions.get(self.asked.get()).copied().flatten();
+            self.asked.set(self.asked.get() + 1);
+            direction
+        }
+
+        fn reset(&mut self) {
+            self.asked.set(0);
+        }
+
+        fn is_human(&self) -> bool {
+            self.human
+        }
+
+        fn name(&self) -> &'static str {
+            if self.human { "HUMAN" } else { "BOT" }
+        }
+    }
+
+    #[test]
+    fn multi_controller_prefers_the_first_and_falls_through() {
+        let sim = Simulation::new(GameConfig::new(GameMode::Normal, Board::SMALL, BoundaryRule::Wrap), 0);
+        let (first, first_asked) = Scripted::boxed(&[Some(Direction::Up), None, None], true);
+        let (second, second_asked) = Scripted::boxed(&[Some(Direction::Left), Some(Direction::Down), None], false);
+        let mut multi = MultiController::new(vec![first, second]);
+
+        assert_eq!(multi.next_direction(&sim), Some(Direction::Up));
+        assert_eq!(multi.next_direction(&sim), Some(Direction::Down));
+        assert_eq!(multi.next_direction(&sim), None);
+        // ถามทุกตัวทุกครั้ง แม้ตัวแรกจะมีทิศแล้ว
+        assert_eq!((first_asked.get(), second_asked.get()), (3, 3));
+
+        multi.reset();
+        assert_eq!((first_asked.get(), second_asked.get()), (0, 0));
+        assert!(multi.is_human());
+        assert_eq!(multi.name(), "HUMAN");
+        assert_eq!(MultiController::new(Vec::new()).name(), "NONE");
+    }
+}
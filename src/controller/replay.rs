@@ -0,0 +1,53 @@
+use crate::controller::Controller;
+use crate::game::game_state::Direction;
+use crate::game::replay::ReplayPlayer;
+use crate::game::simulation::Simulation;
+
+// เล่นตาม input ที่บันทึกไว้ในไฟล์ replay ทีละ tick
+impl Controller for ReplayPlayer {
+    fn next_direction(&mut self, sim: &Simulation) -> Option<Direction> {
+        self.input_for_tick(sim.tick)
+    }
+
+    fn name(&self) -> &'static str {
+        "REPLAY"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller;
+    use crate::game::board::Board;
+    use crate::game::config::{BoundaryRule, GameConfig};
+    use crate::game::game_state::GameMode;
+    use crate::game::replay::Replay;
+
+    #[test]
+    fn returns_recorded_inputs_on_their_ticks() {
+        let config = GameConfig::new(GameMode::Normal, Board::new(20, 15), BoundaryRule::Wrap);
+        let mut replay = Replay::new(5, config.clone());
+        replay.record(4, Direction::Up);
+        replay.record(9, Direction::Left);
+        replay.record(9, Direction::Down);
+        replay.record(30, Direction::Right);
+
+        let mut player = ReplayPlayer::new(replay);
+        let mut sim = Simulation::new(config, 5);
+        let mut seen = Vec::new();
+        while sim.tick < 60 {
+            if let Some(direction) = player.next_direction(&sim) {
+                seen.push((sim.tick, direction));
+            }
+            sim.step(None);
+        }
+        // สอง input ใน tick เดียวกันใช้อันหลัง
+        assert_eq!(seen, [(4, Direction::Up), (9, Direction::Down), (30, Direction::Right)]);
+        // เลยท้าย replay ไปแล้วไม่มี input อีก งูไปทางเดิม
+        for _ in 0..30 {
+            assert_eq!(player.next_direction(&sim), None);
+            controller::step(&mut player, &mut sim);
+        }
+        assert_eq!(sim.snake.direction, Direction::Right);
+    }
+}
@@ -0,0 +1,209 @@
+use ::rand::Rng;
+use std::collections::VecDeque;
+use crate::game::config::BoundaryRule;
+use crate::game::game_state::{Direction, Position};
+
+pub const MIN_BOARD_SIZE: i32 = 5;
+pub const MAX_BOARD_SIZE: i32 = 200;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BoardPreset {
+    Small,
+    Medium,
+    Large,
+    Custom,
+}
+
+impl BoardPreset {
+    pub fn name(self) -> &'static str {
+        match self {
+            BoardPreset::Small => "small",
+            BoardPreset::Medium => "medium",
+            BoardPreset::Large => "large",
+            BoardPreset::Custom => "custom",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "small" => Some(BoardPreset::Small),
+            "medium" => Some(BoardPreset::Medium),
+            "large" => Some(BoardPreset::Large),
+            "custom" => Some(BoardPreset::Custom),
+            _ => None,
+        }
+    }
+}
+
+// ขนาดกระดานของเกม (จำนวนช่อง) กำหนดตอนรันแทนค่าคงที่
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Board {
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Board::MEDIUM
+    }
+}
+
+impl Board {
+    pub const SMALL: Board = Board { width: 20, height: 15 };
+    pub const MEDIUM: Board = Board { width: 40, height: 30 };
+    pub const LARGE: Board = Board { width: 60, height: 45 };
+
+    pub fn new(width: i32, height: i32) -> Self {
+        Board {
+            width: width.clamp(MIN_BOARD_SIZE, MAX_BOARD_SIZE),
+            height: height.clamp(MIN_BOARD_SIZE, MAX_BOARD_SIZE),
+        }
+    }
+
+    pub fn from_preset(preset: BoardPreset, custom: Board) -> Self {
+        match preset {
+            BoardPreset::Small => Board::SMALL,
+            BoardPreset::Medium => Board::MEDIUM,
+            BoardPreset::Large => Board::LARGE,
+            BoardPreset::Custom => Board::new(custom.width, custom.height),
+        }
+    }
+
+    pub fn preset(&self) -> BoardPreset {
+        match *self {
+            Board::SMALL => BoardPreset::Small,
+            Board::MEDIUM => BoardPreset::Medium,
+            Board::LARGE => BoardPreset::Large,
+            _ => BoardPreset::Custom,
+        }
+    }
+
+    pub fn cell_count(&self) -> usize {
+        (self.width * self.height) as usize
+    }
+
+    pub fn center(&self) -> Position {
+        Position {
+            x: self.width / 2,
+            y: self.height / 2,
+        }
+    }
+
+    pub fn contains(&self, position: &Position) -> bool {
+        position.x >= 0 && position.x < self.width && position.y >= 0 && position.y < self.height
+    }
+
+    // เดินทะลุขอบ (wrap around)
+    pub fn wrap(&self, position: Position) -> Position {
+        Position {
+            x: position.x.rem_euclid(self.width),
+            y: position.y.rem_euclid(self.height),
+        }
+    }
+
+    // ลำดับของช่องในอาร์เรย์ที่มีขนาด cell_count
+    pub fn index(&self, position: &Position) -> usize {
+        (position.y * self.width + position.x) as usize
+    }
+
+    // ช่องข้างเคียงตามกติกาขอบ คืนค่า None ถ้าออกนอกกระดานแบบ solid/bounce
+    pub fn neighbor(&self, position: Position, direction: Direction, boundary: BoundaryRule) -> Option<Position> {
+        let next = direction.offset(position);
+        match boundary {
+            BoundaryRule::Wrap => Some(self.wrap(next)),
+            BoundaryRule::Solid | BoundaryRule::Bounce => Some(next).filter(|p| self.contains(p)),
+        }
+    }
+
+    // ระยะ Manhattan ระหว่างสองช่อง ถ้าขอบทะลุได้นับทางที่อ้อมขอบด้วย
+    pub fn distance(&self, a: Position, b: Position, boundary: BoundaryRule) -> i32 {
+        let dx = (a.x - b.x).abs();
+        let dy = (a.y - b.y).abs();
+        match boundary {
+            BoundaryRule::Wrap => dx.min(self.width - dx) + dy.min(self.height - dy),
+            BoundaryRule::Solid | BoundaryRule::Bounce => dx + dy,
+        }
+    }
+
+    // หาช่องที่เดินไปถึงได้จาก start โดยไม่ผ่านช่องที่ blocked (ทั้งสองอาร์เรย์เรียงตาม index)
+    pub fn flood_fill(&self, start: Position, boundary: BoundaryRule, blocked: &[bool]) -> Vec<bool> {
+        let mut reached = vec![false; self.cell_count()];
+        if blocked[self.index(&start)] {
+            return reached;
+        }
+        let mut queue = VecDeque::new();
+        reached[self.index(&start)] = true;
+        queue.push_back(start);
+        while let Some(position) = queue.pop_front() {
+            for direction in Direction::ALL {
+                if let Some(next) = self.neighbor(position, direction, boundary) {
+                    let index = self.index(&next);
+                    if !blocked[index] && !reached[index] {
+                        reached[index] = true;
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+        reached
+    }
+
+    pub fn random_position(&self, rng: &mut impl Rng) -> Position {
+        Position {
+            x: rng.gen_range(0..self.width),
+            y: rng.gen_range(0..self.height),
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        format!("{}x{}", self.width, self.height)
+    }
+
+    pub fn from_text(text: &str) -> Option<Self> {
+        let (width, height) = text.trim().split_once('x')?;
+        Some(Board::new(width.parse().ok()?, height.parse().ok()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamps_size() {
+        assert_eq!(Board::new(1, 500), Board { width: MIN_BOARD_SIZE, height: MAX_BOARD_SIZE });
+        assert_eq!(Board::new(33, 21).cell_count(), 33 * 21);
+    }
+
+    #[test]
+    fn parses_size_text() {
+        assert_eq!(Board::from_text(" 32x24 "), Some(Board { width: 32, height: 24 }));
+        assert_eq!(Board::from_text("2x999"), Some(Board::new(MIN_BOARD_SIZE, MAX_BOARD_SIZE)));
+        assert_eq!(Board::from_text(&Board::LARGE.to_text()), Some(Board::LARGE));
+        for text in ["", "32", "32x", "x24", "ax24", "32*24"] {
+            assert_eq!(Board::from_text(text), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn maps_presets() {
+        for preset in [BoardPreset::Small, BoardPreset::Medium, BoardPreset::Large] {
+            let board = Board::from_preset(preset, Board::default());
+            assert_eq!(board.preset(), preset);
+            assert_eq!(BoardPreset::from_name(preset.name()), Some(preset));
+        }
+        let custom = Board::from_preset(BoardPreset::Custom, Board::new(25, 25));
+        assert_eq!(custom, Board::new(25, 25));
+        assert_eq!(custom.preset(), BoardPreset::Custom);
+    }
+
+    #[test]
+    fn wraps_and_indexes_positions() {
+        let board = Board::new(10, 6);
+        assert_eq!(board.wrap(Position { x: -1, y: 6 }), Position { x: 9, y: 0 });
+        assert_eq!(board.wrap(Position { x: 10, y: -1 }), Position { x: 0, y: 5 });
+        assert_eq!(board.index(&Position { x: 9, y: 5 }), board.cell_count() - 1);
+        assert_eq!(board.center(), Position { x: 5, y: 3 });
+        assert!(!board.contains(&Position { x: 10, y: 0 }));
+    }
+}
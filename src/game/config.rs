@@ -0,0 +1,95 @@
+use std::sync::Arc;
+use crate::game::board::Board;
+use crate::game::game_state::GameMode;
+use crate::game::level::Level;
+
+// สิ่งที่เกิดขึ้นเมื่องูวิ่งออกนอกขอบกระดาน
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BoundaryRule {
+    Wrap,
+    Solid,
+    Bounce,
+}
+
+impl BoundaryRule {
+    pub const ALL: [BoundaryRule; 3] = [BoundaryRule::Wrap, BoundaryRule::Solid, BoundaryRule::Bounce];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BoundaryRule::Wrap => "wrap",
+            BoundaryRule::Solid => "solid",
+            BoundaryRule::Bounce => "bounce",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "wrap" => Some(BoundaryRule::Wrap),
+            "solid" => Some(BoundaryRule::Solid),
+            "bounce" => Some(BoundaryRule::Bounce),
+            _ => None,
+        }
+    }
+}
+
+// ทุกอย่างที่กำหนดกติกาของเกมหนึ่งรอบ (นอกจาก seed)
+#[derive(Clone, PartialEq, Debug)]
+pub struct GameConfig {
+    pub game_mode: GameMode,
+    pub board: Board,
+    pub boundary: BoundaryRule,
+    // ด่านที่เขียนเอง (None = สุ่มสิ่งกีดขวางตามปกติ)
+    pub level: Option<Arc<Level>>,
+    // โหมด Maze: สร้างเขาวงกตใหม่ทุกครั้งที่งูยาวครบทุก ๆ N ช่อง (None = ไม่สร้างใหม่)
+    pub maze_regen_length: Option<usize>,
+    // โอกาสเกิด Power-up ต่อการขยับ (None = ค่ามาตรฐาน) ด่านที่ปิด Power-up ไว้จะไม่เกิดเลย
+    pub power_up_chance: Option<f32>,
+    // กินอาหารครบแต่ละจำนวนในนี้แล้วงูเร็วขึ้นอีกขั้น (ว่าง = ความเร็วคงที่)
+    pub speed_thresholds: Vec<u32>,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            game_mode: GameMode::Normal,
+            board: Board::default(),
+            boundary: BoundaryRule::Wrap,
+            level: None,
+            maze_regen_length: None,
+            power_up_chance: None,
+            speed_thresholds: Vec::new(),
+        }
+    }
+}
+
+impl GameConfig {
+    pub fn new(game_mode: GameMode, board: Board, boundary: BoundaryRule) -> Self {
+        GameConfig {
+            game_mode,
+            board,
+            boundary,
+            level: None,
+            maze_regen_length: None,
+            power_up_chance: None,
+            speed_thresholds: Vec::new(),
+        }
+    }
+
+    // ขนาดกระดานและกติกาขอบมาจากไฟล์ด่าน ด่านที่มีเป้าหมายนับเป็นโหมด Campaign
+    pub fn for_level(level: Arc<Level>) -> Self {
+        GameConfig {
+            game_mode: if level.is_campaign() { GameMode::Campaign } else { GameMode::Obstacle },
+            board: level.board,
+            boundary: level.boundary,
+            level: Some(level),
+            maze_regen_length: None,
+            power_up_chance: None,
+            speed_thresholds: Vec::new(),
+        }
+    }
+}
+
+// อ่าน speed_thresholds แบบคั่นด้วยจุลภาค เช่น "10,20,40"
+pub fn parse_speed_thresholds(text: &str) -> Option<Vec<u32>> {
+    text.split(',').map(|part| part.trim().parse().ok()).collect()
+}
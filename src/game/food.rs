@@ -1,34 +1,34 @@
 use ::rand::Rng;
-use ::rand::thread_rng;
-use std::collections::VecDeque;
 use crate::game::game_state::Position;
-use crate::utils::{GRID_WIDTH, GRID_HEIGHT};
+use crate::game::occupancy::Occupancy;
 
 pub struct Food {
     pub position: Position,
 }
 
 impl Food {
-    pub fn new() -> Self {
-        Food {
-            position: Self::random_position(&VecDeque::new()),
-        }
+    pub fn new(position: Position) -> Self {
+        Food { position }
     }
 
-    pub fn random_position(snake: &VecDeque<Position>) -> Position {
-        let mut rng = thread_rng();
-        loop {
-            let pos = Position {
-                x: rng.gen_range(0..GRID_WIDTH),
-                y: rng.gen_range(0..GRID_HEIGHT),
-            };
-            if !snake.contains(&pos) {
-                return pos;
+    // สุ่มจากช่องว่างที่เหลือ คืนค่า false ถ้ากระดานเต็มแล้ว
+    pub fn respawn(&mut self, occupancy: &Occupancy, rng: &mut impl Rng) -> bool {
+        match occupancy.random_free(rng) {
+            Some(position) => {
+                self.position = position;
+                true
             }
+            None => false,
         }
     }
 
-    pub fn respawn(&mut self, snake: &VecDeque<Position>) {
-        self.position = Self::random_position(snake);
+    // ด่านที่กำหนดจุด F ไว้ สุ่มเฉพาะจุดที่ยังว่าง ถ้าไม่มีเหลือเลยค่อยสุ่มทั้งกระดาน
+    pub fn respawn_at_spots(&mut self, spots: &[Position], occupancy: &Occupancy, rng: &mut impl Rng) -> bool {
+        let free: Vec<&Position> = spots.iter().filter(|spot| occupancy.is_free(spot)).collect();
+        if free.is_empty() {
+            return self.respawn(occupancy, rng);
+        }
+        self.position = *free[rng.gen_range(0..free.len())];
+        true
     }
-} 
\ No newline at end of file
+}
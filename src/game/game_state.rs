@@ -1,4 +1,4 @@
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum Direction {
     Up,
     Down,
@@ -6,7 +6,60 @@ pub enum Direction {
     Right,
 }
 
-#[derive(Copy, Clone, PartialEq)]
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+    // ช่องถัดไปในทิศนี้ (ยังไม่ wrap)
+    pub fn offset(self, position: Position) -> Position {
+        match self {
+            Direction::Up => Position { x: position.x, y: position.y - 1 },
+            Direction::Down => Position { x: position.x, y: position.y + 1 },
+            Direction::Left => Position { x: position.x - 1, y: position.y },
+            Direction::Right => Position { x: position.x + 1, y: position.y },
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    // ทิศทางจากช่อง from ไปช่อง to ที่อยู่ติดกัน
+    pub fn between(from: Position, to: Position) -> Option<Self> {
+        match (to.x - from.x, to.y - from.y) {
+            (0, -1) => Some(Direction::Up),
+            (0, 1) => Some(Direction::Down),
+            (-1, 0) => Some(Direction::Left),
+            (1, 0) => Some(Direction::Right),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Direction::Up => 'U',
+            Direction::Down => 'D',
+            Direction::Left => 'L',
+            Direction::Right => 'R',
+        }
+    }
+
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'U' => Some(Direction::Up),
+            'D' => Some(Direction::Down),
+            'L' => Some(Direction::Left),
+            'R' => Some(Direction::Right),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum PowerUpType {
     SpeedBoost,
     Shrink,
@@ -17,10 +70,10 @@ pub enum PowerUpType {
 pub struct PowerUp {
     pub position: Position,
     pub power_type: PowerUpType,
-    pub duration: u32, // frames
+    pub duration: u32, // ticks
 }
 
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Position {
     pub x: i32,
     pub y: i32,
@@ -32,10 +85,41 @@ pub enum GameState {
     Playing,
     Paused,
     GameOver,
+    Replay,
+    Leaderboard,
+    Settings,
+    LevelSelect,
+    // งูยาวเต็มกระดาน
+    Victory,
 }
 
-#[derive(PartialEq)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum GameMode {
     Normal,
     Obstacle,
-} 
\ No newline at end of file
+    Campaign,
+    Maze,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 4] = [GameMode::Normal, GameMode::Obstacle, GameMode::Campaign, GameMode::Maze];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GameMode::Normal => "normal",
+            GameMode::Obstacle => "obstacle",
+            GameMode::Campaign => "campaign",
+            GameMode::Maze => "maze",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "normal" => Some(GameMode::Normal),
+            "obstacle" => Some(GameMode::Obstacle),
+            "campaign" => Some(GameMode::Campaign),
+            "maze" => Some(GameMode::Maze),
+            _ => None,
+        }
+    }
+}
\ No newline at end of file
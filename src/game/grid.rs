@@ -0,0 +1,70 @@
+use crate::game::board::Board;
+use crate::game::game_state::Position;
+
+// ค่าหนึ่งค่าต่อช่องบนกระดาน อ่าน/เขียนได้ O(1) ตามตำแหน่ง
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid<T> {
+    board: Board,
+    cells: Vec<T>,
+}
+
+impl<T: Copy + Default> Grid<T> {
+    pub fn new(board: Board) -> Self {
+        Grid {
+            board,
+            cells: vec![T::default(); board.cell_count()],
+        }
+    }
+
+    pub fn board(&self) -> Board {
+        self.board
+    }
+
+    // ช่องนอกกระดานได้ค่า default
+    pub fn get(&self, position: &Position) -> T {
+        if self.board.contains(position) {
+            self.cells[self.board.index(position)]
+        } else {
+            T::default()
+        }
+    }
+
+    pub fn set(&mut self, position: &Position, value: T) {
+        if self.board.contains(position) {
+            let index = self.board.index(position);
+            self.cells[index] = value;
+        }
+    }
+
+    pub fn fill(&mut self, value: T) {
+        self.cells.fill(value);
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    // ตำแหน่งของทุกช่องที่ค่าตรงตามเงื่อนไข
+    pub fn positions_where(&self, predicate: impl Fn(T) -> bool) -> Vec<Position> {
+        let width = self.board.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| predicate(**value))
+            .map(|(index, _)| Position {
+                x: index as i32 % width,
+                y: index as i32 / width,
+            })
+            .collect()
+    }
+}
+
+impl Grid<bool> {
+    pub fn from_positions(board: Board, positions: &[Position]) -> Self {
+        let mut grid = Grid::new(board);
+        for position in positions {
+            grid.set(position, true);
+        }
+        grid
+    }
+}
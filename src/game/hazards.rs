@@ -0,0 +1,254 @@
+use crate::game::game_state::Position;
+
+// ช่วงเวลาทั้งหมดนับเป็น tick ของ Simulation
+#[derive(Clone, Debug, PartialEq)]
+pub enum HazardKind {
+    // สิ่งกีดขวางหนึ่งช่องวิ่งไปกลับตาม route ขยับหนึ่งช่องทุก step_ticks
+    Patrol { route: Vec<Position>, step_ticks: u64 },
+    // ประตูปิด (ตัน) closed_ticks แล้วเปิด open_ticks วนไปเรื่อย ๆ
+    Gate { closed_ticks: u64, open_ticks: u64 },
+    // ทุก ๆ period: ว่าง, เตือน warning_ticks, แล้วตัน active_ticks
+    Periodic { period: u64, warning_ticks: u64, active_ticks: u64 },
+}
+
+// สิ่งกีดขวางเคลื่อนที่/ตั้งเวลาที่ประกาศไว้ในไฟล์ด่าน
+#[derive(Clone, Debug, PartialEq)]
+pub struct HazardSpec {
+    pub kind: HazardKind,
+    // ช่องของ Gate/Periodic (Patrol ใช้ route แทน)
+    pub cells: Vec<Position>,
+    // เลื่อนจังหวะเริ่มต้นออกไปกี่ tick
+    pub offset: u64,
+}
+
+impl HazardSpec {
+    // ทุกช่องที่สิ่งนี้อาจไปอยู่ได้ ใช้กันไม่ให้อาหาร/Power-up เกิดทับ
+    pub fn zone(&self) -> &[Position] {
+        match &self.kind {
+            HazardKind::Patrol { route, .. } => route,
+            _ => &self.cells,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum HazardPhase {
+    Open,
+    // กระพริบเตือนก่อนจะตัน ยังเดินผ่านได้
+    Warning,
+    Solid,
+}
+
+// สถานะระหว่างเล่นของ HazardSpec หนึ่งตัว
+#[derive(Clone, Debug)]
+pub struct Hazard {
+    pub spec: HazardSpec,
+    pub phase: HazardPhase,
+    // tick ที่เหลือก่อนจะขยับหรือเปลี่ยน phase
+    timer: u64,
+    route_index: usize,
+    forward: bool,
+}
+
+impl Hazard {
+    pub fn new(spec: HazardSpec) -> Self {
+        let (phase, first) = match spec.kind {
+            HazardKind::Patrol { step_ticks, .. } => (HazardPhase::Solid, step_ticks),
+            HazardKind::Gate { closed_ticks, .. } => (HazardPhase::Solid, closed_ticks),
+            HazardKind::Periodic { period, warning_ticks, active_ticks } => {
+                (HazardPhase::Open, period - warning_ticks - active_ticks)
+            }
+        };
+        Hazard {
+            timer: spec.offset + first,
+            spec,
+            phase,
+            route_index: 0,
+            forward: true,
+        }
+    }
+
+    pub fn cells(&self) -> Vec<Position> {
+        match &self.spec.kind {
+            HazardKind::Patrol { route, .. } => vec![route[self.route_index]],
+            _ => self.spec.cells.clone(),
+        }
+    }
+
+    pub fn solid_cells(&self) -> Vec<Position> {
+        if self.phase == HazardPhase::Solid {
+            self.cells()
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn next_route_index(&self, route_len: usize) -> (usize, bool) {
+        if route_len < 2 {
+            return (self.route_index, self.forward);
+        }
+        let forward = match (self.forward, self.route_index) {
+            (true, i) if i + 1 == route_len => false,
+            (false, 0) => true,
+            (forward, _) => forward,
+        };
+        let index = if forward { self.route_index + 1 } else { self.route_index - 1 };
+        (index, forward)
+    }
+
+    // เดินเวลาหนึ่ง tick ถ้าช่องที่จะกลายเป็นของตันมีงูอยู่ (occupied) จะรอ tick ถัดไปแทนการทับงู
+    pub fn tick(&mut self, occupied: impl Fn(&Position) -> bool) {
+        if self.timer > 1 {
+            self.timer -= 1;
+            return;
+        }
+
+        match self.spec.kind.clone() {
+            HazardKind::Patrol { route, step_ticks } => {
+                let (index, forward) = self.next_route_index(route.len());
+                if occupied(&route[index]) {
+                    return;
+                }
+                self.route_index = index;
+                self.forward = forward;
+                self.timer = step_ticks;
+            }
+            HazardKind::Gate { closed_ticks, open_ticks } => match self.phase {
+                HazardPhase::Solid => {
+                    self.phase = HazardPhase::Open;
+                    self.timer = open_ticks;
+                }
+                _ => {
+                    if self.spec.cells.iter().any(&occupied) {
+                        return;
+                    }
+                    self.phase = HazardPhase::Solid;
+                    self.timer = closed_ticks;
+                }
+            },
+            HazardKind::Periodic { period, warning_ticks, active_ticks } => match self.phase {
+                HazardPhase::Open => {
+                    self.phase = HazardPhase::Warning;
+                    self.timer = warning_ticks;
+                }
+                HazardPhase::Warning => {
+                    if self.spec.cells.iter().any(&occupied) {
+                        return;
+                    }
+                    self.phase = HazardPhase::Solid;
+                    self.timer = active_ticks;
+                }
+                HazardPhase::Solid => {
+                    self.phase = HazardPhase::Open;
+                    self.timer = period - warning_ticks - active_ticks;
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::game::config::GameConfig;
+    use crate::game::game_state::Direction;
+    use crate::game::level::Level;
+    use crate::game::simulation::{DeathCause, GameEvent, Simulation};
+
+    fn at(x: i32, y: i32) -> Position {
+        Position { x, y }
+    }
+
+    fn hazard(kind: HazardKind, cells: Vec<Position>, offset: u64) -> Hazard {
+        Hazard::new(HazardSpec { kind, cells, offset })
+    }
+
+    // เดินเวลาทีละ tick แล้วเก็บ phase กับช่องหลังแต่ละ tick
+    fn run(hazard: &mut Hazard, ticks: usize, occupied: impl Fn(&Position) -> bool) -> Vec<(HazardPhase, Position)> {
+        (0..ticks)
+            .map(|_| {
+                hazard.tick(&occupied);
+                (hazard.phase, hazard.cells()[0])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn patrol_walks_and_turns_back() {
+        let route = vec![at(0, 0), at(1, 0), at(2, 0)];
+        let mut patrol = hazard(HazardKind::Patrol { route, step_ticks: 2 }, Vec::new(), 0);
+        assert_eq!(patrol.solid_cells(), [at(0, 0)]);
+        let cells: Vec<Position> = run(&mut patrol, 10, |_| false).into_iter().map(|(_, cell)| cell).collect();
+        let x: Vec<i32> = cells.iter().map(|cell| cell.x).collect();
+        assert_eq!(x, [0, 1, 1, 2, 2, 1, 1, 0, 0, 1]);
+        assert_eq!(patrol.phase, HazardPhase::Solid);
+    }
+
+    // มีงูขวางช่องถัดไป patrol รออยู่ที่เดิมจนกว่างูจะพ้นไป
+    #[test]
+    fn patrol_waits_for_the_snake() {
+        let route = vec![at(0, 0), at(1, 0)];
+        let mut patrol = hazard(HazardKind::Patrol { route, step_ticks: 1 }, Vec::new(), 0);
+        run(&mut patrol, 3, |cell| *cell == at(1, 0));
+        assert_eq!(patrol.cells(), [at(0, 0)]);
+        run(&mut patrol, 1, |_| false);
+        assert_eq!(patrol.cells(), [at(1, 0)]);
+    }
+
+    #[test]
+    fn gate_opens_and_closes_on_time() {
+        let kind = HazardKind::Gate { closed_ticks: 3, open_ticks: 2 };
+        let mut gate = hazard(kind, vec![at(4, 1), at(4, 2)], 1);
+        let phases: Vec<HazardPhase> = run(&mut gate, 10, |_| false).into_iter().map(|(phase, _)| phase).collect();
+        use HazardPhase::{Open, Solid};
+        assert_eq!(phases, [Solid, Solid, Solid, Open, Open, Solid, Solid, Solid, Open, Open]);
+        assert_eq!(gate.solid_cells(), []);
+
+        // งูยืนอยู่ในช่องประตู ประตูเปิดค้างไว้จนกว่างูจะออก
+        run(&mut gate, 5, |cell| *cell == at(4, 2));
+        assert_eq!(gate.phase, Open);
+        run(&mut gate, 1, |_| false);
+        assert_eq!(gate.solid_cells(), [at(4, 1), at(4, 2)]);
+    }
+
+    #[test]
+    fn periodic_warns_before_turning_solid() {
+        let kind = HazardKind::Periodic { period: 6, warning_ticks: 2, active_ticks: 1 };
+        let mut periodic = hazard(kind, vec![at(3, 3)], 0);
+        assert_eq!(periodic.phase, HazardPhase::Open);
+        let phases: Vec<HazardPhase> = run(&mut periodic, 12, |_| false).into_iter().map(|(phase, _)| phase).collect();
+        use HazardPhase::{Open, Solid, Warning};
+        assert_eq!(phases, [Open, Open, Warning, Warning, Solid, Open, Open, Open, Warning, Warning, Solid, Open]);
+
+        // ช่วงเตือนที่มีงูอยู่ ยังไม่ตันจนกว่างูจะพ้น
+        run(&mut periodic, 4, |_| false);
+        assert_eq!(periodic.phase, HazardPhase::Warning);
+        run(&mut periodic, 3, |_| true);
+        assert_eq!(periodic.phase, HazardPhase::Warning);
+    }
+
+    fn play_into_gate(gate: &str) -> (Simulation, Vec<GameEvent>) {
+        let text = format!("gate: 4,2 {}\npower_ups: off\n---\n.......\n.......\n.S.....\n.......\n.......\n", gate);
+        let level = Level::parse("test", &text).unwrap();
+        let mut sim = Simulation::new(GameConfig::for_level(Arc::new(level)), 1);
+        let mut events = Vec::new();
+        while !sim.is_over() && sim.snake.body[0].x < 5 {
+            events.extend(sim.step(Some(Direction::Right)));
+        }
+        (sim, events)
+    }
+
+    #[test]
+    fn closed_gate_kills_the_snake() {
+        let (sim, events) = play_into_gate("closed 10000 open 10");
+        assert!(events.contains(&GameEvent::Died(DeathCause::Obstacle)));
+        assert_eq!(sim.death_cause, Some(DeathCause::Obstacle));
+        assert_eq!(sim.snake.body[0], at(3, 2));
+
+        // ประตูเปิดก่อนงูมาถึง เดินผ่านไปได้
+        let (sim, _) = play_into_gate("closed 1 open 10000");
+        assert_eq!(sim.death_cause, None);
+        assert_eq!(sim.snake.body[0], at(5, 2));
+    }
+}
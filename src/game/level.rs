@@ -0,0 +1,586 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use crate::game::board::{Board, MAX_BOARD_SIZE, MIN_BOARD_SIZE};
+use crate::game::config::BoundaryRule;
+use crate::game::game_state::{Direction, Position};
+use crate::game::hazards::{HazardKind, HazardSpec};
+
+const GRID_SEPARATOR: &str = "---";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LevelError {
+    // 0 = ไม่ได้ชี้ไปที่ตำแหน่งใดในไฟล์ (เช่นอ่านไฟล์ไม่ได้)
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl LevelError {
+    fn at(line: usize, column: usize, message: impl Into<String>) -> Self {
+        LevelError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.column) {
+            (0, _) => write!(f, "{}", self.message),
+            (line, 0) => write!(f, "line {}: {}", line, self.message),
+            (line, column) => write!(f, "line {}, column {}: {}", line, column, self.message),
+        }
+    }
+}
+
+// เป้าหมายของด่านในโหมด Campaign
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum LevelGoal {
+    Length(usize),
+    Food(u32),
+    // วินาที
+    Survive(u64),
+}
+
+impl LevelGoal {
+    fn parse(value: &str) -> Option<Self> {
+        let (kind, amount) = value.split_once(char::is_whitespace)?;
+        let amount = amount.trim();
+        let goal = match kind {
+            "length" => LevelGoal::Length(amount.parse().ok()?),
+            "food" => LevelGoal::Food(amount.parse().ok()?),
+            "survive" => LevelGoal::Survive(amount.parse().ok()?),
+            _ => return None,
+        };
+        Some(goal).filter(|goal| goal.target() > 0)
+    }
+
+    pub fn target(&self) -> u64 {
+        match *self {
+            LevelGoal::Length(length) => length as u64,
+            LevelGoal::Food(food) => food as u64,
+            LevelGoal::Survive(seconds) => seconds,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            LevelGoal::Length(length) => format!("Reach length {}", length),
+            LevelGoal::Food(food) => format!("Eat {} food", food),
+            LevelGoal::Survive(seconds) => format!("Survive {} seconds", seconds),
+        }
+    }
+}
+
+// ด่านที่เขียนเองจากไฟล์ข้อความ
+//
+//   name: Pillars          <- ส่วนหัวแบบ key: value (บรรทัดที่ขึ้นต้นด้วย ; คือคอมเมนต์)
+//   direction: right
+//   boundary: solid
+//   power_ups: off
+//   goal: food 5           <- มี goal = เป็นด่านของ Campaign (length N, food N, survive วินาที)
+//   patrol: 2,1 7,1 step 15               <- วิ่งไปกลับตามจุด (แนวตรงเท่านั้น)
+//   gate: 5,2 5,3 closed 180 open 120     <- ปิด/เปิดสลับกัน
+//   hazard: 3,3 period 300 warning 60 active 90   <- เตือนก่อนแล้วค่อยตัน
+//   (ตัวเลขเวลาเป็น tick, ใส่ offset N เพื่อเลื่อนจังหวะได้ทุกแบบ)
+//   ---
+//   ##########             <- ตาราง: # กำแพง, . ช่องว่าง, S จุดเกิด, F จุดวางอาหาร
+//   #S.....F.#
+//   ##########
+#[derive(Clone, Debug, PartialEq)]
+pub struct Level {
+    pub id: String,
+    pub name: String,
+    pub board: Board,
+    pub walls: Vec<Position>,
+    pub spawn: Position,
+    pub direction: Direction,
+    pub food_spots: Vec<Position>,
+    pub boundary: BoundaryRule,
+    pub power_ups: bool,
+    pub goal: Option<LevelGoal>,
+    pub hazards: Vec<HazardSpec>,
+}
+
+fn parse_direction(value: &str) -> Option<Direction> {
+    match value {
+        "up" => Some(Direction::Up),
+        "down" => Some(Direction::Down),
+        "left" => Some(Direction::Left),
+        "right" => Some(Direction::Right),
+        _ => None,
+    }
+}
+
+fn parse_switch(value: &str) -> Option<bool> {
+    match value {
+        "on" | "true" | "yes" => Some(true),
+        "off" | "false" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_cell(token: &str) -> Option<Position> {
+    let (x, y) = token.split_once(',')?;
+    Some(Position {
+        x: x.trim().parse().ok()?,
+        y: y.trim().parse().ok()?,
+    })
+}
+
+// ขยายจุดหักเลี้ยวให้เป็นทุกช่องตามเส้นทาง จุดที่ติดกันต้องอยู่แถวหรือคอลัมน์เดียวกัน
+fn expand_route(waypoints: &[Position]) -> Result<Vec<Position>, String> {
+    let mut route = vec![waypoints[0]];
+    for pair in waypoints.windows(2) {
+        let (from, to) = (pair[0], pair[1]);
+        if from.x != to.x && from.y != to.y {
+            return Err(format!("{},{} and {},{} are not in a straight line", from.x, from.y, to.x, to.y));
+        }
+        let (dx, dy) = ((to.x - from.x).signum(), (to.y - from.y).signum());
+        let mut cell = from;
+        while cell != to {
+            cell = Position { x: cell.x + dx, y: cell.y + dy };
+            route.push(cell);
+        }
+    }
+    Ok(route)
+}
+
+// รูปแบบ: รายการช่อง x,y ตามด้วยคู่ชื่อ/ค่า เช่น "2,1 7,1 step 15 offset 30"
+fn parse_hazard(key: &str, value: &str) -> Result<HazardSpec, String> {
+    let mut cells = Vec::new();
+    let mut params: Vec<(&str, u64)> = Vec::new();
+    let mut tokens = value.split_whitespace();
+    while let Some(token) = tokens.next() {
+        if token.contains(',') {
+            cells.push(parse_cell(token).ok_or_else(|| format!("invalid cell '{}'", token))?);
+            continue;
+        }
+        let number = tokens
+            .next()
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(|| format!("'{}' needs a number", token))?;
+        params.push((token, number));
+    }
+    if cells.is_empty() {
+        return Err("no cells given".to_string());
+    }
+
+    let allowed: &[&str] = match key {
+        "patrol" => &["step", "offset"],
+        "gate" => &["closed", "open", "offset"],
+        _ => &["period", "warning", "active", "offset"],
+    };
+    if let Some((name, _)) = params.iter().find(|(name, _)| !allowed.contains(name)) {
+        return Err(format!("unknown {} setting '{}'", key, name));
+    }
+    let param = |name: &str| -> Result<u64, String> {
+        params
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, value)| *value)
+            .filter(|value| *value > 0)
+            .ok_or_else(|| format!("{} needs '{} N' with N > 0", key, name))
+    };
+    let offset = params.iter().find(|(n, _)| *n == "offset").map_or(0, |(_, value)| *value);
+
+    let kind = match key {
+        "patrol" => HazardKind::Patrol {
+            route: expand_route(&cells)?,
+            step_ticks: param("step")?,
+        },
+        "gate" => HazardKind::Gate {
+            closed_ticks: param("closed")?,
+            open_ticks: param("open")?,
+        },
+        _ => {
+            let (period, warning_ticks, active_ticks) = (param("period")?, param("warning")?, param("active")?);
+            if warning_ticks + active_ticks >= period {
+                return Err("warning + active must be shorter than period".to_string());
+            }
+            HazardKind::Periodic {
+                period,
+                warning_ticks,
+                active_ticks,
+            }
+        }
+    };
+    let cells = if matches!(kind, HazardKind::Patrol { .. }) { Vec::new() } else { cells };
+    Ok(HazardSpec { kind, cells, offset })
+}
+
+impl Level {
+    pub fn parse(id: &str, text: &str) -> Result<Self, LevelError> {
+        let lines: Vec<&str> = text.lines().collect();
+
+        let separator = lines
+            .iter()
+            .position(|line| line.trim() == GRID_SEPARATOR)
+            .ok_or_else(|| LevelError::at(0, 0, format!("missing '{}' line before the grid", GRID_SEPARATOR)))?;
+
+        let mut level = Level {
+            id: id.to_string(),
+            name: id.to_string(),
+            board: Board::default(),
+            walls: Vec::new(),
+            spawn: Position { x: 0, y: 0 },
+            direction: Direction::Right,
+            food_spots: Vec::new(),
+            boundary: BoundaryRule::Solid,
+            power_ups: true,
+            goal: None,
+            hazards: Vec::new(),
+        };
+        // ตรวจตำแหน่งของ hazard หลังอ่านตารางแล้ว จึงเก็บบรรทัด/คอลัมน์ไว้ก่อน
+        let mut hazard_lines = Vec::new();
+        let mut goal_line = (0, 0);
+
+        for (index, line) in lines[..separator].iter().enumerate() {
+            let line_number = index + 1;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with(';') {
+                continue;
+            }
+            let (key, value) = trimmed
+                .split_once(':')
+                .ok_or_else(|| LevelError::at(line_number, 1, "expected 'key: value'"))?;
+            let (key, value) = (key.trim(), value.trim());
+            // คอลัมน์ของค่านับจากหลัง ':' (ค่าอาจซ้ำกับข้อความใน key)
+            let (before, after) = line.split_once(':').unwrap_or((line, ""));
+            let value_column = before.chars().count() + 2 + (after.chars().count() - after.trim_start().chars().count());
+            let invalid = |what: &str| LevelError::at(line_number, value_column, format!("invalid {} '{}'", what, value));
+
+            match key {
+                "name" => level.name = value.to_string(),
+                "direction" => level.direction = parse_direction(value).ok_or_else(|| invalid("direction"))?,
+                "boundary" => level.boundary = BoundaryRule::from_name(value).ok_or_else(|| invalid("boundary"))?,
+                "power_ups" => level.power_ups = parse_switch(value).ok_or_else(|| invalid("switch"))?,
+                "goal" => {
+                    level.goal = Some(LevelGoal::parse(value).ok_or_else(|| invalid("goal"))?);
+                    goal_line = (line_number, value_column);
+                }
+                "patrol" | "gate" | "hazard" => {
+                    let spec = parse_hazard(key, value).map_err(|e| LevelError::at(line_number, value_column, e))?;
+                    hazard_lines.push((line_number, value_column));
+                    level.hazards.push(spec);
+                }
+                _ => return Err(LevelError::at(line_number, 1, format!("unknown key '{}'", key))),
+            }
+        }
+
+        let grid_start = separator + 1;
+        let mut rows: Vec<&str> = lines[grid_start..].to_vec();
+        while rows.last().is_some_and(|row| row.trim().is_empty()) {
+            rows.pop();
+        }
+
+        let height = rows.len() as i32;
+        let width = rows.first().map_or(0, |row| row.chars().count()) as i32;
+        if !(MIN_BOARD_SIZE..=MAX_BOARD_SIZE).contains(&width) || !(MIN_BOARD_SIZE..=MAX_BOARD_SIZE).contains(&height) {
+            return Err(LevelError::at(
+                grid_start + 1,
+                0,
+                format!(
+                    "grid is {}x{}, must be between {} and {} cells on each side",
+                    width, height, MIN_BOARD_SIZE, MAX_BOARD_SIZE
+                ),
+            ));
+        }
+        level.board = Board::new(width, height);
+
+        let mut spawn = None;
+        for (y, row) in rows.iter().enumerate() {
+            let line_number = grid_start + y + 1;
+            let row_width = row.chars().count() as i32;
+            if row_width != width {
+                return Err(LevelError::at(
+                    line_number,
+                    row_width.min(width) as usize + 1,
+                    format!("row is {} cells wide, expected {}", row_width, width),
+                ));
+            }
+            for (x, c) in row.chars().enumerate() {
+                let position = Position { x: x as i32, y: y as i32 };
+                match c {
+                    '#' => level.walls.push(position),
+                    '.' | ' ' => {}
+                    'F' => level.food_spots.push(position),
+                    'S' => {
+                        if spawn.is_some() {
+                            return Err(LevelError::at(line_number, x + 1, "more than one spawn point 'S'"));
+                        }
+                        spawn = Some(position);
+                    }
+                    other => {
+                        return Err(LevelError::at(line_number, x + 1, format!("unknown cell '{}'", other)));
+                    }
+                }
+            }
+        }
+
+        level.spawn = spawn.ok_or_else(|| LevelError::at(grid_start + 1, 0, "no spawn point 'S' in grid"))?;
+        level.validate(grid_start)?;
+        level.validate_goal().map_err(|e| LevelError::at(goal_line.0, goal_line.1, e))?;
+        for (spec, (line, column)) in level.hazards.iter().zip(hazard_lines) {
+            level.validate_hazard(spec).map_err(|e| LevelError::at(line, column, e))?;
+        }
+        Ok(level)
+    }
+
+    pub fn is_campaign(&self) -> bool {
+        self.goal.is_some()
+    }
+
+    pub fn is_wall(&self, position: &Position) -> bool {
+        self.walls.contains(position)
+    }
+
+    // ช่องที่งูเข้าไปตอนขยับครั้งแรก ขอบแบบ bounce งูเด้งกลับไปทางตรงข้าม
+    fn first_move(&self) -> Option<Position> {
+        match self.board.neighbor(self.spawn, self.direction, self.boundary) {
+            None if self.boundary == BoundaryRule::Bounce => {
+                self.board.neighbor(self.spawn, self.direction.opposite(), self.boundary)
+            }
+            first => first,
+        }
+    }
+
+    // ตรวจว่าด่านเล่นได้จริง: ก้าวแรกไม่ชนกำแพง และทุกช่องว่างเดินไปถึงได้จากจุดเกิด
+    fn validate(&self, grid_start: usize) -> Result<(), LevelError> {
+        let cell_error = |position: Position, message: &str| {
+            LevelError::at(grid_start + position.y as usize + 1, position.x as usize + 1, message)
+        };
+
+        let board = self.board;
+        match self.first_move() {
+            Some(first) if !self.is_wall(&first) => {}
+            _ => return Err(cell_error(self.spawn, "the first move from the spawn point hits a wall")),
+        }
+
+        let mut blocked = vec![false; board.cell_count()];
+        for wall in &self.walls {
+            blocked[board.index(wall)] = true;
+        }
+        let reached = board.flood_fill(self.spawn, self.boundary, &blocked);
+
+        if reached.iter().filter(|r| **r).count() < 2 {
+            return Err(cell_error(self.spawn, "the spawn point is walled in"));
+        }
+
+        for y in 0..board.height {
+            for x in 0..board.width {
+                let position = Position { x, y };
+                let index = board.index(&position);
+                if !blocked[index] && !reached[index] {
+                    let message = if self.food_spots.contains(&position) {
+                        "food spot cannot be reached from the spawn point"
+                    } else {
+                        "cell cannot be reached from the spawn point"
+                    };
+                    return Err(cell_error(position, message));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // เป้าหมายต้องไปถึงได้จริง งูยาวได้ไม่เกินจำนวนช่องว่าง และเริ่มที่ยาว 1 จึงกินได้น้อยกว่านั้นหนึ่ง
+    fn validate_goal(&self) -> Result<(), String> {
+        let open_cells = self.board.cell_count() - self.walls.len();
+        match self.goal {
+            Some(LevelGoal::Length(length)) if length > open_cells => {
+                Err(format!("goal needs length {} but the grid has only {} open cells", length, open_cells))
+            }
+            Some(LevelGoal::Food(food)) if food as usize >= open_cells => Err(format!(
+                "goal needs {} food but at most {} fit in {} open cells",
+                food,
+                open_cells - 1,
+                open_cells
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    // hazard ต้องอยู่บนช่องว่างในกระดาน และไม่ขวางจุดเกิดหรือก้าวแรก
+    fn validate_hazard(&self, spec: &HazardSpec) -> Result<(), String> {
+        let first_move = self.first_move();
+        for cell in spec.zone() {
+            if !self.board.contains(cell) {
+                return Err(format!("{},{} is outside the grid", cell.x, cell.y));
+            }
+            if self.is_wall(cell) {
+                return Err(format!("{},{} is a wall", cell.x, cell.y));
+            }
+            if *cell == self.spawn || Some(*cell) == first_move {
+                return Err(format!("{},{} blocks the spawn point", cell.x, cell.y));
+            }
+            if self.food_spots.contains(cell) {
+                return Err(format!("{},{} is a food spot", cell.x, cell.y));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, LevelError> {
+        let text = fs::read_to_string(path).map_err(|e| LevelError::at(0, 0, e.to_string()))?;
+        let id = path.file_stem().map_or_else(String::new, |stem| stem.to_string_lossy().into_owned());
+        Self::parse(&id, &text)
+    }
+}
+
+pub struct LevelLoadError {
+    pub file_name: String,
+    pub error: LevelError,
+}
+
+// โหลดทุกไฟล์ .txt ในโฟลเดอร์ เรียงตามชื่อไฟล์ ไฟล์ที่ผิดรูปแบบจะถูกแยกไว้ใน errors
+pub fn load_levels(dir: &Path) -> (Vec<Level>, Vec<LevelLoadError>) {
+    let mut levels = Vec::new();
+    let mut errors = Vec::new();
+
+    let mut paths: Vec<_> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+            .collect(),
+        Err(_) => return (levels, errors),
+    };
+    paths.sort();
+
+    for path in paths {
+        match Level::load(&path) {
+            Ok(level) => levels.push(level),
+            Err(error) => errors.push(LevelLoadError {
+                file_name: path.file_name().map_or_else(String::new, |n| n.to_string_lossy().into_owned()),
+                error,
+            }),
+        }
+    }
+    (levels, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRID: &str = "\
+.....
+.....
+..S..
+.....
+.....
+";
+
+    fn parse(header: &str) -> Result<Level, LevelError> {
+        Level::parse("test", &format!("{}---\n{}", header, GRID))
+    }
+
+    // หันเข้าขอบตอนเกิดชนกำแพงเฉพาะขอบแบบ solid ขอบแบบ bounce งูเด้งกลับได้
+    #[test]
+    fn spawn_facing_edge_depends_on_boundary() {
+        let grid = "---\n.....\n.....\n....S\n.....\n.....\n";
+        let solid = Level::parse("test", &format!("direction: right\nboundary: solid\n{}", grid)).unwrap_err();
+        assert_eq!((solid.line, solid.column), (6, 5));
+        assert!(Level::parse("test", &format!("direction: right\nboundary: bounce\n{}", grid)).is_ok());
+        assert!(Level::parse("test", &format!("direction: right\nboundary: wrap\n{}", grid)).is_ok());
+
+        let walled = "---\n.....\n.....\n...#S\n.....\n.....\n";
+        assert!(Level::parse("test", &format!("direction: right\nboundary: bounce\n{}", walled)).is_err());
+    }
+
+    // คอลัมน์ของค่านับจากหลัง ':' แม้ค่าจะซ้ำกับชื่อ key
+    #[test]
+    fn value_column_follows_colon() {
+        let error = parse("direction: di\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 12));
+        let error = parse("  boundary:   bound\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 15));
+    }
+
+    fn error_at(text: &str) -> (usize, usize, String) {
+        let error = Level::parse("test", text).unwrap_err();
+        (error.line, error.column, error.message)
+    }
+
+    #[test]
+    fn parses_header_and_grid() {
+        let level = parse("name: Tiny\ndirection: up\nboundary: wrap\npower_ups: off\ngoal: food 3\n").unwrap();
+        assert_eq!(level.name, "Tiny");
+        assert_eq!(level.direction, Direction::Up);
+        assert_eq!(level.boundary, BoundaryRule::Wrap);
+        assert!(!level.power_ups);
+        assert_eq!(level.goal, Some(LevelGoal::Food(3)));
+        assert_eq!(level.spawn, Position { x: 2, y: 2 });
+        assert_eq!((level.board.width, level.board.height), (5, 5));
+    }
+
+    #[test]
+    fn reports_header_errors() {
+        assert_eq!(error_at(".....\n"), (0, 0, "missing '---' line before the grid".to_string()));
+        let cases = [
+            ("oops\n", 1, 1, "expected 'key: value'"),
+            ("; comment\ncolour: red\n", 2, 1, "unknown key 'colour'"),
+            ("boundary: sticky\n", 1, 11, "invalid boundary 'sticky'"),
+            ("power_ups: maybe\n", 1, 12, "invalid switch 'maybe'"),
+            ("goal: food 0\n", 1, 7, "invalid goal 'food 0'"),
+            ("gate: 1,1 closed 10\n", 1, 7, "gate needs 'open N' with N > 0"),
+            ("patrol: 0,0 1,1 step 5\n", 1, 9, "0,0 and 1,1 are not in a straight line"),
+            ("hazard: 0,0 period 10 warning 5 active 5\n", 1, 9, "warning + active must be shorter than period"),
+        ];
+        for (header, line, column, message) in cases {
+            assert_eq!(parse(header).unwrap_err(), LevelError::at(line, column, message), "{}", header);
+        }
+    }
+
+    #[test]
+    fn reports_grid_errors() {
+        let cases = [
+            ("---\n....\n....\n..S.\n....\n", 2, 0, "grid is 4x4, must be between 5 and 200 cells on each side"),
+            ("---\n.....\n....\n..S..\n.....\n.....\n", 3, 5, "row is 4 cells wide, expected 5"),
+            ("---\n.....\n..x..\n..S..\n.....\n.....\n", 3, 3, "unknown cell 'x'"),
+            ("---\n.S...\n.....\n..S..\n.....\n.....\n", 4, 3, "more than one spawn point 'S'"),
+            ("---\n.....\n.....\n.....\n.....\n.....\n", 2, 0, "no spawn point 'S' in grid"),
+            ("---\n.....\n.###.\n.#S#.\n.###.\n.....\n", 4, 3, "the first move from the spawn point hits a wall"),
+            ("direction: left\n---\n...##\n..S#F\n...##\n.....\n.....\n", 4, 5, "food spot cannot be reached from the spawn point"),
+        ];
+        for (text, line, column, message) in cases {
+            assert_eq!(error_at(text), (line, column, message.to_string()), "{}", text);
+        }
+    }
+
+    #[test]
+    fn reports_hazard_position_errors() {
+        let cases = [
+            ("gate: 9,9 closed 10 open 10\n", "9,9 is outside the grid"),
+            ("gate: 2,2 closed 10 open 10\n", "2,2 blocks the spawn point"),
+            ("gate: 3,2 closed 10 open 10\n", "3,2 blocks the spawn point"),
+        ];
+        for (header, message) in cases {
+            assert_eq!(parse(header).unwrap_err(), LevelError::at(1, 7, message), "{}", header);
+        }
+    }
+
+    // กระดาน 5x5 ไม่มีกำแพง มีช่องว่าง 25 ช่อง
+    #[test]
+    fn rejects_goals_larger_than_the_board() {
+        assert!(parse("goal: length 25\n").is_ok());
+        assert!(parse("goal: food 24\n").is_ok());
+        assert!(parse("goal: survive 9999\n").is_ok());
+        let cases = [
+            ("goal: length 26\n", 1, 7, "goal needs length 26 but the grid has only 25 open cells"),
+            ("name: Big\ngoal:  food 25\n", 2, 8, "goal needs 25 food but at most 24 fit in 25 open cells"),
+        ];
+        for (header, line, column, message) in cases {
+            assert_eq!(parse(header).unwrap_err(), LevelError::at(line, column, message), "{}", header);
+        }
+
+        // กำแพงลดจำนวนช่องที่ใช้ได้
+        let walled = |goal: &str| Level::parse("test", &format!("goal: {}\n---\n#....\n.....\n..S..\n.....\n.....\n", goal));
+        assert!(walled("length 24").is_ok());
+        let message = "goal needs length 25 but the grid has only 24 open cells";
+        assert_eq!(walled("length 25").unwrap_err(), LevelError::at(1, 7, message));
+    }
+}
@@ -1,9 +1,27 @@
+pub mod board;
+pub mod config;
 pub mod game_state;
+pub mod grid;
+pub mod hazards;
+pub mod level;
+pub mod obstacles;
+pub mod occupancy;
 pub mod snake;
 pub mod food;
 pub mod power_ups;
+pub mod replay;
+pub mod simulation;
 
+pub use board::{Board, BoardPreset};
+pub use config::{parse_speed_thresholds, BoundaryRule, GameConfig};
 pub use game_state::*;
+pub use grid::Grid;
+pub use hazards::{Hazard, HazardKind, HazardPhase, HazardSpec};
+pub use level::{Level, LevelError, LevelGoal, LevelLoadError};
+pub use occupancy::{CellCounts, Layer, Occupancy};
+pub use obstacles::{generate_maze, generate_obstacles, ObstaclePattern, MAZE_MODE_OPENING_CHANCE};
 pub use snake::Snake;
 pub use food::Food;
-pub use power_ups::PowerUpManager; 
\ No newline at end of file
+pub use power_ups::PowerUpManager;
+pub use replay::{Replay, ReplayPlayer};
+pub use simulation::{Simulation, SimulationState, GameEvent, DeathCause, BASE_MOVES_PER_SECOND, SPEED_STEP, TICKS_PER_SECOND}; 
\ No newline at end of file
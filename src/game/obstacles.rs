@@ -0,0 +1,578 @@
+use ::rand::seq::SliceRandom;
+use ::rand::Rng;
+use std::collections::VecDeque;
+use crate::game::board::Board;
+use crate::game::config::BoundaryRule;
+use crate::game::game_state::{Direction, Position};
+use crate::game::grid::Grid;
+
+// ช่องว่างที่รับประกันไว้หน้าจุดเกิด (ยาว x กว้าง)
+const SAFE_ZONE_LENGTH: i32 = 6;
+const SAFE_ZONE_HALF_WIDTH: i32 = 1;
+// รูปแบบ Maze ในโหมด Obstacle เปิดกำแพงเพิ่มตามสัดส่วนนี้ ไม่ให้ทางตันเยอะเกินไป
+const MAZE_OPENING_CHANCE: f64 = 0.35;
+// โหมด Maze เปิดกำแพงเพิ่มเล็กน้อยให้มีทางวนบ้าง
+pub const MAZE_MODE_OPENING_CHANCE: f64 = 0.08;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ObstaclePattern {
+    Clusters,
+    Lines,
+    Rooms,
+    Maze,
+}
+
+impl ObstaclePattern {
+    pub const ALL: [ObstaclePattern; 4] = [
+        ObstaclePattern::Clusters,
+        ObstaclePattern::Lines,
+        ObstaclePattern::Rooms,
+        ObstaclePattern::Maze,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ObstaclePattern::Clusters => "clusters",
+            ObstaclePattern::Lines => "lines",
+            ObstaclePattern::Rooms => "rooms",
+            ObstaclePattern::Maze => "maze",
+        }
+    }
+}
+
+// วางสิ่งกีดขวางทีละก้อน ก้อนไหนทำให้มีช่องว่างที่เดินไปไม่ถึงจากจุดเกิดจะถูกทิ้ง
+struct Layout {
+    board: Board,
+    boundary: BoundaryRule,
+    spawn: Position,
+    blocked: Grid<bool>,
+    // ช่องที่ห้ามวางสิ่งกีดขวาง (จุดเกิด, safe zone และตัวงู)
+    reserved: Grid<bool>,
+    free_cells: usize,
+}
+
+impl Layout {
+    fn new(board: Board, boundary: BoundaryRule, spawn: Position, direction: Direction) -> Self {
+        let mut reserved = Grid::new(board);
+        let side = match direction {
+            Direction::Up | Direction::Down => Direction::Right,
+            Direction::Left | Direction::Right => Direction::Down,
+        };
+        let mut center = spawn;
+        for _ in 0..SAFE_ZONE_LENGTH {
+            reserved.set(&center, true);
+            for sign in [side, side.opposite()] {
+                let mut cell = center;
+                for _ in 0..SAFE_ZONE_HALF_WIDTH {
+                    match board.neighbor(cell, sign, boundary) {
+                        Some(next) => {
+                            reserved.set(&next, true);
+                            cell = next;
+                        }
+                        None => break,
+                    }
+                }
+            }
+            match board.neighbor(center, direction, boundary) {
+                Some(next) => center = next,
+                None => break,
+            }
+        }
+
+        Layout {
+            board,
+            boundary,
+            spawn,
+            blocked: Grid::new(board),
+            reserved,
+            free_cells: board.cell_count(),
+        }
+    }
+
+    fn is_connected(&self) -> bool {
+        let reached = self.board.flood_fill(self.spawn, self.boundary, self.blocked.cells());
+        reached.iter().filter(|r| **r).count() == self.free_cells
+    }
+
+    fn block(&mut self, position: &Position) {
+        if !self.blocked.get(position) {
+            self.blocked.set(position, true);
+            self.free_cells -= 1;
+        }
+    }
+
+    fn unblock(&mut self, position: &Position) {
+        if self.blocked.get(position) {
+            self.blocked.set(position, false);
+            self.free_cells += 1;
+        }
+    }
+
+    // คืนค่า true ถ้าวางได้ทั้งก้อนโดยทุกช่องว่างยังเดินถึงได้
+    fn try_place(&mut self, cells: &[Position]) -> bool {
+        let mut placed = Vec::new();
+        for cell in cells {
+            if self.board.contains(cell) && !self.reserved.get(cell) && !self.blocked.get(cell) {
+                self.block(cell);
+                placed.push(*cell);
+            }
+        }
+        if placed.is_empty() || self.is_connected() {
+            return !placed.is_empty();
+        }
+        for cell in &placed {
+            self.unblock(cell);
+        }
+        false
+    }
+
+    // เปลี่ยนช่องว่างที่เดินไปไม่ถึงให้เป็นกำแพง
+    fn fill_pockets(&mut self) {
+        let reached = self.board.flood_fill(self.spawn, self.boundary, self.blocked.cells());
+        for cell in self.blocked.positions_where(|blocked| !blocked) {
+            if !reached[self.board.index(&cell)] {
+                self.block(&cell);
+            }
+        }
+    }
+
+    // เปิดทางที่สั้นที่สุดจากจุดเกิดไปยังช่อง target ที่ใกล้ที่สุด ผ่านกำแพงได้แต่ไม่ผ่านช่องใน avoid
+    fn carve_path_to(&mut self, is_target: impl Fn(&Position) -> bool, avoid: &Grid<bool>) {
+        let board = self.board;
+        let mut previous: Grid<Option<Position>> = Grid::new(board);
+        let mut seen = Grid::new(board);
+        let mut queue = VecDeque::new();
+        seen.set(&self.spawn, true);
+        queue.push_back(self.spawn);
+        while let Some(position) = queue.pop_front() {
+            if position != self.spawn && is_target(&position) {
+                let mut cell = position;
+                while cell != self.spawn {
+                    self.unblock(&cell);
+                    cell = previous.get(&cell).unwrap_or(self.spawn);
+                }
+                return;
+            }
+            for direction in Direction::ALL {
+                if let Some(next) = board.neighbor(position, direction, self.boundary) {
+                    if !seen.get(&next) && !avoid.get(&next) {
+                        seen.set(&next, true);
+                        previous.set(&next, Some(position));
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+    }
+
+    fn free_neighbors(&self, position: Position) -> Vec<Position> {
+        Direction::ALL
+            .iter()
+            .filter_map(|direction| self.board.neighbor(position, *direction, self.boundary))
+            .filter(|next| !self.blocked.get(next))
+            .collect()
+    }
+
+    // ลบทางตันทั้งหมด งูกลับหลังไม่ได้ เข้าทางตันแล้วต้องตาย
+    // ช่องที่มีทางออกไม่เกินหนึ่งทาง เปิดกำแพงข้าง ๆ ที่ต่อไปหาทางเดินอื่นได้
+    // ถ้าไม่มีกำแพงแบบนั้นก็ถมช่องนั้นแทน (ยกเว้นช่องที่จองไว้)
+    fn braid(&mut self, rng: &mut impl Rng) {
+        let board = self.board;
+        for _ in 0..board.cell_count() {
+            let mut changed = false;
+            for cell in self.blocked.positions_where(|blocked| !blocked) {
+                if self.blocked.get(&cell) || self.free_neighbors(cell).len() > 1 {
+                    continue;
+                }
+                let openings: Vec<Position> = Direction::ALL
+                    .iter()
+                    .filter_map(|direction| board.neighbor(cell, *direction, self.boundary))
+                    .filter(|wall| self.blocked.get(wall))
+                    .filter(|wall| self.free_neighbors(*wall).iter().any(|next| *next != cell))
+                    .collect();
+                if let Some(wall) = openings.choose(rng) {
+                    self.unblock(wall);
+                    changed = true;
+                } else if !self.reserved.get(&cell) {
+                    self.block(&cell);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    // ช่องคอขวดที่ถ้าตันแล้วกระดานขาดเป็นสองส่วน ส่วนที่ไม่มีจุดเกิดคือซอกที่เข้าออกได้ทางเดียว
+    // งูยาวเข้าไปกินอาหารในซอกแล้วตัวเองขวางทางออก จึงเปิดกำแพงให้ซอกต่อกับทางเดินอื่น
+    // ถ้าเปิดไม่ได้ก็ถมทั้งซอก (ยกเว้นซอกที่มีช่องที่จองไว้) คืนค่า true ถ้ามีการเปลี่ยนแปลง
+    fn open_pockets(&mut self, rng: &mut impl Rng) -> bool {
+        let board = self.board;
+        let mut changed = false;
+        let cuts = self.cut_cells();
+        for cell in self.blocked.positions_where(|blocked| !blocked) {
+            if cell == self.spawn || !cuts.get(&cell) || self.blocked.get(&cell) || self.free_neighbors(cell).len() < 2 {
+                continue;
+            }
+            let mut blocked = self.blocked.cells().to_vec();
+            blocked[board.index(&cell)] = true;
+            let main = board.flood_fill(self.spawn, self.boundary, &blocked);
+            let pocket: Vec<Position> = self
+                .blocked
+                .positions_where(|blocked| !blocked)
+                .into_iter()
+                .filter(|position| *position != cell && !main[board.index(position)])
+                .collect();
+            if pocket.is_empty() {
+                continue;
+            }
+
+            let mut openings: Vec<Position> = pocket
+                .iter()
+                .flat_map(|position| Direction::ALL.map(|direction| board.neighbor(*position, direction, self.boundary)))
+                .flatten()
+                .filter(|wall| self.blocked.get(wall))
+                .filter(|wall| {
+                    self.free_neighbors(*wall).iter().any(|next| *next != cell && main[board.index(next)])
+                })
+                .collect();
+            openings.sort_by_key(|wall| board.index(wall));
+            openings.dedup();
+            if let Some(wall) = openings.choose(rng) {
+                self.unblock(wall);
+                changed = true;
+            } else if pocket.iter().all(|position| !self.reserved.get(position)) {
+                for position in &pocket {
+                    self.block(position);
+                }
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    // หาช่องคอขวดทั้งหมดรอบเดียวด้วย DFS (Tarjan) แทนการลองตันทีละช่องแล้ว flood fill ใหม่
+    // เขียนแบบวนลูปเองเพราะกระดานใหญ่ทำให้ recursion ลึกเกินไป
+    fn cut_cells(&self) -> Grid<bool> {
+        let board = self.board;
+        let mut cuts = Grid::new(board);
+        let mut order = vec![0; board.cell_count()];
+        let mut low = vec![0; board.cell_count()];
+        let mut parent: Vec<Option<Position>> = vec![None; board.cell_count()];
+        let mut counter = 1;
+        let mut root_children = 0;
+        order[board.index(&self.spawn)] = counter;
+        low[board.index(&self.spawn)] = counter;
+        let mut stack = vec![(self.spawn, 0)];
+        while let Some((cell, next_direction)) = stack.last_mut() {
+            let cell = *cell;
+            let index = board.index(&cell);
+            if *next_direction < Direction::ALL.len() {
+                let direction = Direction::ALL[*next_direction];
+                *next_direction += 1;
+                let next = match board.neighbor(cell, direction, self.boundary) {
+                    Some(next) if !self.blocked.get(&next) => next,
+                    _ => continue,
+                };
+                let next_index = board.index(&next);
+                if order[next_index] == 0 {
+                    counter += 1;
+                    order[next_index] = counter;
+                    low[next_index] = counter;
+                    parent[next_index] = Some(cell);
+                    stack.push((next, 0));
+                } else if parent[index] != Some(next) {
+                    low[index] = low[index].min(order[next_index]);
+                }
+                continue;
+            }
+            stack.pop();
+            if let Some((above, _)) = stack.last() {
+                let above_index = board.index(above);
+                low[above_index] = low[above_index].min(low[index]);
+                if *above == self.spawn {
+                    root_children += 1;
+                } else if low[index] >= order[above_index] {
+                    cuts.set(above, true);
+                }
+            }
+        }
+        cuts.set(&self.spawn, root_children > 1);
+        cuts
+    }
+
+    // ลบทางตันและซอกจนไม่เหลือ ถมซอกแล้วอาจเกิดทางตันใหม่จึงทำซ้ำจนนิ่ง
+    fn remove_traps(&mut self, rng: &mut impl Rng) {
+        for _ in 0..self.board.cell_count() {
+            self.braid(rng);
+            if !self.open_pockets(rng) {
+                break;
+            }
+        }
+    }
+
+    fn into_positions(self) -> Vec<Position> {
+        self.blocked.positions_where(|blocked| blocked)
+    }
+}
+
+fn random_cluster(board: &Board, rng: &mut impl Rng) -> Vec<Position> {
+    let mut cells = vec![board.random_position(rng)];
+    let size = rng.gen_range(3..=7);
+    while cells.len() < size {
+        let from = cells[rng.gen_range(0..cells.len())];
+        let next = Direction::ALL[rng.gen_range(0..4)].offset(from);
+        if board.contains(&next) && !cells.contains(&next) {
+            cells.push(next);
+        }
+    }
+    cells
+}
+
+fn random_line(board: &Board, rng: &mut impl Rng) -> Vec<Position> {
+    let max_length = (board.width.min(board.height) / 3).max(3);
+    let length = rng.gen_range(3..=max_length);
+    let direction = if rng.gen() { Direction::Right } else { Direction::Down };
+    let mut cell = board.random_position(rng);
+    let mut cells = Vec::new();
+    for _ in 0..length {
+        cells.push(cell);
+        cell = direction.offset(cell);
+    }
+    cells
+}
+
+// ห้องสี่เหลี่ยมที่มีประตูหนึ่งถึงสองช่อง
+fn random_room(board: &Board, rng: &mut impl Rng) -> Vec<Position> {
+    let width = rng.gen_range(5..=(board.width / 3).max(5));
+    let height = rng.gen_range(4..=(board.height / 3).max(4));
+    let left = rng.gen_range(0..=(board.width - width).max(0));
+    let top = rng.gen_range(0..=(board.height - height).max(0));
+
+    let mut cells = Vec::new();
+    for x in left..left + width {
+        cells.push(Position { x, y: top });
+        cells.push(Position { x, y: top + height - 1 });
+    }
+    for y in top + 1..top + height - 1 {
+        cells.push(Position { x: left, y });
+        cells.push(Position { x: left + width - 1, y });
+    }
+
+    // มุมห้องไม่ใช้เป็นประตู
+    let doors = rng.gen_range(1..=2);
+    for _ in 0..doors {
+        let candidates: Vec<usize> = (0..cells.len())
+            .filter(|&i| {
+                let c = cells[i];
+                let corner = (c.x == left || c.x == left + width - 1) && (c.y == top || c.y == top + height - 1);
+                !corner
+            })
+            .collect();
+        if let Some(&door) = candidates.choose(rng) {
+            cells.swap_remove(door);
+        }
+    }
+    cells
+}
+
+fn is_maze_room(position: &Position) -> bool {
+    position.x % 2 == 1 && position.y % 2 == 1
+}
+
+// recursive backtracker บนช่องพิกัดคี่ แล้วเปิดกำแพงเพิ่มตาม opening_chance
+fn carve_maze(layout: &mut Layout, opening_chance: f64, rng: &mut impl Rng) {
+    let board = layout.board;
+    let columns = (board.width - 1) / 2;
+    let rows = (board.height - 1) / 2;
+    let room = |cx: i32, cy: i32| Position { x: cx * 2 + 1, y: cy * 2 + 1 };
+
+    let mut walls = Grid::new(board);
+    walls.fill(true);
+    let mut visited = vec![false; (columns * rows) as usize];
+    let mut stack = vec![(0, 0)];
+    visited[0] = true;
+    walls.set(&room(0, 0), false);
+    while let Some(&(cx, cy)) = stack.last() {
+        let mut neighbors: Vec<(i32, i32)> = [(0, -1), (0, 1), (-1, 0), (1, 0)]
+            .iter()
+            .map(|(dx, dy)| (cx + dx, cy + dy))
+            .filter(|&(nx, ny)| nx >= 0 && ny >= 0 && nx < columns && ny < rows && !visited[(ny * columns + nx) as usize])
+            .collect();
+        neighbors.shuffle(rng);
+        match neighbors.first() {
+            Some(&(nx, ny)) => {
+                visited[(ny * columns + nx) as usize] = true;
+                let from = room(cx, cy);
+                let to = room(nx, ny);
+                walls.set(&to, false);
+                walls.set(&Position { x: (from.x + to.x) / 2, y: (from.y + to.y) / 2 }, false);
+                stack.push((nx, ny));
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+
+    for cell in walls.positions_where(|wall| wall) {
+        if !layout.reserved.get(&cell) && !rng.gen_bool(opening_chance) {
+            layout.block(&cell);
+        }
+    }
+}
+
+// เขาวงกตทั้งกระดาน ช่องของงู (หัวอยู่ตัวแรก) และ safe zone หน้าหัวจะว่างเสมอ
+// ทางเดินทุกช่องต่อถึงหัวงู ช่องที่เดินไปไม่ถึงจะถูกถมเป็นกำแพง และไม่มีทางตันหรือซอกที่เข้าออกได้ทางเดียว
+pub fn generate_maze(
+    board: &Board,
+    boundary: BoundaryRule,
+    snake: &[Position],
+    direction: Direction,
+    opening_chance: f64,
+    rng: &mut impl Rng,
+) -> Vec<Position> {
+    let head = snake.first().copied().unwrap_or_else(|| board.center());
+    let mut layout = Layout::new(*board, boundary, head, direction);
+    let body = Grid::from_positions(*board, snake.get(1..).unwrap_or(&[]));
+    for cell in snake {
+        layout.reserved.set(cell, true);
+    }
+
+    carve_maze(&mut layout, opening_chance, rng);
+    // หัวงูอาจอยู่ในส่วนที่ตัดขาดจากทางเดิน (เช่นชิดขอบ) จึงเจาะทางไปหาห้องของเขาวงกตที่ใกล้ที่สุด
+    let reached = board.flood_fill(head, boundary, layout.blocked.cells());
+    let connected = reached
+        .iter()
+        .enumerate()
+        .any(|(index, r)| *r && is_maze_room(&Position { x: index as i32 % board.width, y: index as i32 / board.width }));
+    if !connected {
+        layout.carve_path_to(is_maze_room, &body);
+    }
+    layout.remove_traps(rng);
+    layout.fill_pockets();
+    layout.into_positions()
+}
+
+// สร้างสิ่งกีดขวางตามรูปแบบ โดยทุกช่องว่างเดินไปถึงได้จากจุดเกิด
+// และหน้าจุดเกิดตามทิศเริ่มต้นว่างเสมอ
+pub fn generate_obstacles(
+    board: &Board,
+    boundary: BoundaryRule,
+    spawn: Position,
+    direction: Direction,
+    pattern: ObstaclePattern,
+    rng: &mut impl Rng,
+) -> Vec<Position> {
+    let cells = board.cell_count();
+    let (shapes, attempts) = match pattern {
+        ObstaclePattern::Clusters => ((cells / 150).max(3), 4),
+        ObstaclePattern::Lines => ((cells / 200).max(3), 4),
+        ObstaclePattern::Rooms => ((cells / 600).clamp(1, 4), 8),
+        ObstaclePattern::Maze => {
+            return generate_maze(board, boundary, &[spawn], direction, MAZE_OPENING_CHANCE, rng);
+        }
+    };
+
+    let mut layout = Layout::new(*board, boundary, spawn, direction);
+
+    let mut placed = 0;
+    for _ in 0..shapes * attempts {
+        if placed == shapes {
+            break;
+        }
+        let shape = match pattern {
+            ObstaclePattern::Clusters => random_cluster(board, rng),
+            ObstaclePattern::Lines => random_line(board, rng),
+            _ => random_room(board, rng),
+        };
+        if layout.try_place(&shape) {
+            placed += 1;
+        }
+    }
+    layout.remove_traps(rng);
+    layout.into_positions()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::rand::rngs::StdRng;
+    use ::rand::SeedableRng;
+
+    const BOARDS: [Board; 3] = [Board { width: 20, height: 15 }, Board { width: 33, height: 21 }, Board { width: 40, height: 30 }];
+    const BOUNDARIES: [BoundaryRule; 2] = [BoundaryRule::Solid, BoundaryRule::Wrap];
+
+    // ทุกแบบสิ่งกีดขวางที่สุ่มด้วย seed ต่าง ๆ พร้อมจุดเกิด
+    fn layouts() -> Vec<(String, Board, BoundaryRule, Position, Vec<Position>)> {
+        let mut layouts = Vec::new();
+        for board in BOARDS {
+            for boundary in BOUNDARIES {
+                let spawn = board.center();
+                let snake = [spawn, Direction::Left.offset(spawn), Direction::Left.offset(Direction::Left.offset(spawn))];
+                for seed in 0..8 {
+                    let mut rng = StdRng::seed_from_u64(seed);
+                    let maze = generate_maze(&board, boundary, &snake, Direction::Right, MAZE_MODE_OPENING_CHANCE, &mut rng);
+                    layouts.push((format!("maze {:?} {:?} seed {}", board, boundary, seed), board, boundary, spawn, maze));
+                    for pattern in ObstaclePattern::ALL {
+                        let mut rng = StdRng::seed_from_u64(seed);
+                        let obstacles = generate_obstacles(&board, boundary, spawn, Direction::Right, pattern, &mut rng);
+                        let name = format!("{} {:?} {:?} seed {}", pattern.name(), board, boundary, seed);
+                        layouts.push((name, board, boundary, spawn, obstacles));
+                    }
+                }
+            }
+        }
+        layouts
+    }
+
+    fn free_neighbors(board: &Board, boundary: BoundaryRule, blocked: &Grid<bool>, position: Position) -> usize {
+        Direction::ALL
+            .iter()
+            .filter_map(|direction| board.neighbor(position, *direction, boundary))
+            .filter(|next| !blocked.get(next))
+            .count()
+    }
+
+    #[test]
+    fn every_free_cell_is_reachable_from_spawn() {
+        for (name, board, boundary, spawn, obstacles) in layouts() {
+            let blocked = Grid::from_positions(board, &obstacles);
+            assert!(!blocked.get(&spawn), "{}", name);
+            let reached = board.flood_fill(spawn, boundary, blocked.cells());
+            for position in blocked.positions_where(|blocked| !blocked) {
+                assert!(reached[board.index(&position)], "{} unreachable {:?}", name, position);
+            }
+        }
+    }
+
+    // งูกลับหลังไม่ได้ ทุกช่องว่างต้องมีทางเข้าออกอย่างน้อยสองทาง
+    #[test]
+    fn has_no_dead_ends() {
+        for (name, board, boundary, _, obstacles) in layouts() {
+            let blocked = Grid::from_positions(board, &obstacles);
+            for position in blocked.positions_where(|blocked| !blocked) {
+                let count = free_neighbors(&board, boundary, &blocked, position);
+                assert!(count >= 2, "{} dead end at {:?}", name, position);
+            }
+        }
+    }
+
+    // ตันช่องใดช่องหนึ่งแล้วช่องว่างที่เหลือยังต้องต่อถึงกันหมด ไม่มีซอกที่เข้าออกได้ทางเดียว
+    // ลองตันทุกช่องตรง ๆ จึงช้า ตรวจเฉพาะกระดานเล็ก
+    #[test]
+    fn has_no_one_way_pockets() {
+        for (name, board, boundary, spawn, obstacles) in layouts().into_iter().filter(|layout| layout.1 == BOARDS[0]) {
+            let blocked = Grid::from_positions(board, &obstacles);
+            let free = blocked.positions_where(|blocked| !blocked);
+            for cell in free.iter().filter(|cell| **cell != spawn) {
+                let mut cut = blocked.cells().to_vec();
+                cut[board.index(cell)] = true;
+                let reached = board.flood_fill(spawn, boundary, &cut);
+                let pocket = free.iter().filter(|position| *position != cell && !reached[board.index(position)]).count();
+                assert_eq!(pocket, 0, "{} pocket behind {:?}", name, cell);
+            }
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use ::rand::Rng;
+use crate::game::board::Board;
+use crate::game::game_state::Position;
+use crate::game::grid::Grid;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Layer {
+    Snake,
+    // obstacles และ hazard ที่ตันอยู่
+    Obstacle,
+    // อาหารและ Power-up
+    Item,
+}
+
+// จำนวนของแต่ละชั้นในช่องหนึ่ง (ตัวงูทับตัวเองได้ตอนเป็นผี จึงต้องนับแทนการใช้ bool)
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct CellCounts {
+    pub snake: u16,
+    pub obstacle: u16,
+    pub item: u16,
+    // ห้ามเกิดของในช่องนี้ (เช่นเส้นทางของ hazard) แต่ไม่นับเป็นการชน
+    pub reserved: bool,
+}
+
+impl CellCounts {
+    pub fn is_free(&self) -> bool {
+        self.snake == 0 && self.obstacle == 0 && self.item == 0 && !self.reserved
+    }
+
+    fn count_mut(&mut self, layer: Layer) -> &mut u16 {
+        match layer {
+            Layer::Snake => &mut self.snake,
+            Layer::Obstacle => &mut self.obstacle,
+            Layer::Item => &mut self.item,
+        }
+    }
+}
+
+// ตารางว่าแต่ละช่องมีอะไรอยู่ พร้อมชุดของช่องว่างสำหรับสุ่มเกิดของแบบ O(1)
+#[derive(Clone, Debug)]
+pub struct Occupancy {
+    cells: Grid<CellCounts>,
+    free: Vec<Position>,
+    // ตำแหน่งของช่องนั้นใน free (None = ไม่ว่าง)
+    free_index: Grid<Option<usize>>,
+}
+
+impl Occupancy {
+    pub fn new(board: Board) -> Self {
+        let cells = Grid::new(board);
+        let free = cells.positions_where(|_| true);
+        let mut free_index = Grid::new(board);
+        for (index, position) in free.iter().enumerate() {
+            free_index.set(position, Some(index));
+        }
+        Occupancy {
+            cells,
+            free,
+            free_index,
+        }
+    }
+
+    pub fn board(&self) -> Board {
+        self.cells.board()
+    }
+
+    pub fn counts(&self, position: &Position) -> CellCounts {
+        self.cells.get(position)
+    }
+
+    pub fn has_snake(&self, position: &Position) -> bool {
+        self.counts(position).snake > 0
+    }
+
+    pub fn has_obstacle(&self, position: &Position) -> bool {
+        self.counts(position).obstacle > 0
+    }
+
+    pub fn is_free(&self, position: &Position) -> bool {
+        self.board().contains(position) && self.counts(position).is_free()
+    }
+
+    pub fn free_count(&self) -> usize {
+        self.free.len()
+    }
+
+    pub fn free_cells(&self) -> &[Position] {
+        &self.free
+    }
+
+    pub fn add(&mut self, position: &Position, layer: Layer) {
+        self.update(position, |counts| *counts.count_mut(layer) += 1);
+    }
+
+    pub fn remove(&mut self, position: &Position, layer: Layer) {
+        self.update(position, |counts| {
+            let count = counts.count_mut(layer);
+            *count = count.saturating_sub(1);
+        });
+    }
+
+    pub fn reserve(&mut self, position: &Position) {
+        self.update(position, |counts| counts.reserved = true);
+    }
+
+    // สุ่มช่องว่าง คืนค่า None ถ้ากระดานเต็ม
+    pub fn random_free(&self, rng: &mut impl Rng) -> Option<Position> {
+        if self.free.is_empty() {
+            return None;
+        }
+        Some(self.free[rng.gen_range(0..self.free.len())])
+    }
+
+    fn update(&mut self, position: &Position, change: impl FnOnce(&mut CellCounts)) {
+        if !self.board().contains(position) {
+            return;
+        }
+        let mut counts = self.cells.get(position);
+        change(&mut counts);
+        self.cells.set(position, counts);
+
+        match (counts.is_free(), self.free_index.get(position)) {
+            (true, None) => {
+                self.free_index.set(position, Some(self.free.len()));
+                self.free.push(*position);
+            }
+            (false, Some(index)) => {
+                self.free.swap_remove(index);
+                if let Some(moved) = self.free.get(index).copied() {
+                    self.free_index.set(&moved, Some(index));
+                }
+                self.free_index.set(position, None);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::rand::rngs::StdRng;
+    use ::rand::SeedableRng;
+
+    fn board() -> Board {
+        Board::new(5, 5)
+    }
+
+    fn all_positions() -> Vec<Position> {
+        Grid::<bool>::new(board()).positions_where(|_| true)
+    }
+
+    // ชุดช่องว่างต้องตรงกับตาราง และ free_index ต้องชี้ถูกตำแหน่งทุกช่อง
+    fn assert_consistent(occupancy: &Occupancy) {
+        for position in all_positions() {
+            let free = occupancy.counts(&position).is_free();
+            assert_eq!(occupancy.is_free(&position), free);
+            match occupancy.free_index.get(&position) {
+                Some(index) => {
+                    assert!(free, "{:?} is in the free set but occupied", position);
+                    assert_eq!(occupancy.free[index], position);
+                }
+                None => assert!(!free, "{:?} is free but missing from the free set", position),
+            }
+        }
+        let expected = all_positions().iter().filter(|p| occupancy.counts(p).is_free()).count();
+        assert_eq!(occupancy.free_count(), expected);
+    }
+
+    #[test]
+    fn starts_with_every_cell_free() {
+        let occupancy = Occupancy::new(board());
+        assert_eq!(occupancy.free_count(), 25);
+        assert_consistent(&occupancy);
+    }
+
+    #[test]
+    fn swap_remove_keeps_free_set_in_sync() {
+        let mut occupancy = Occupancy::new(board());
+        let positions = all_positions();
+        // เอาช่องแรก ๆ ออกเพื่อให้ swap_remove ย้ายช่องท้ายมาแทนที่
+        for position in positions.iter().step_by(3) {
+            occupancy.add(position, Layer::Obstacle);
+            assert_consistent(&occupancy);
+        }
+        for position in positions.iter().step_by(6) {
+            occupancy.remove(position, Layer::Obstacle);
+            assert_consistent(&occupancy);
+        }
+        assert_eq!(occupancy.free_count(), 25 - 9 + 5);
+    }
+
+    #[test]
+    fn counts_each_layer_separately() {
+        let mut occupancy = Occupancy::new(board());
+        let cell = Position { x: 2, y: 2 };
+        occupancy.add(&cell, Layer::Snake);
+        occupancy.add(&cell, Layer::Snake);
+        occupancy.add(&cell, Layer::Obstacle);
+        assert_eq!(occupancy.counts(&cell), CellCounts { snake: 2, obstacle: 1, item: 0, reserved: false });
+
+        occupancy.remove(&cell, Layer::Snake);
+        assert!(occupancy.has_snake(&cell));
+        occupancy.remove(&cell, Layer::Snake);
+        occupancy.remove(&cell, Layer::Snake);
+        assert!(!occupancy.has_snake(&cell));
+        assert!(occupancy.has_obstacle(&cell));
+        assert!(!occupancy.is_free(&cell));
+
+        occupancy.remove(&cell, Layer::Obstacle);
+        assert!(occupancy.is_free(&cell));
+        assert_consistent(&occupancy);
+    }
+
+    #[test]
+    fn reserved_cells_are_not_free_but_not_solid() {
+        let mut occupancy = Occupancy::new(board());
+        let cell = Position { x: 1, y: 3 };
+        occupancy.reserve(&cell);
+        assert!(!occupancy.is_free(&cell));
+        assert!(!occupancy.has_obstacle(&cell));
+        assert_eq!(occupancy.free_count(), 24);
+        assert_consistent(&occupancy);
+    }
+
+    #[test]
+    fn random_free_picks_free_cells_until_full() {
+        let mut occupancy = Occupancy::new(board());
+        let mut rng = StdRng::seed_from_u64(7);
+        occupancy.add(&Position { x: -1, y: 0 }, Layer::Item);
+        assert_eq!(occupancy.free_count(), 25);
+        while let Some(position) = occupancy.random_free(&mut rng) {
+            assert!(occupancy.is_free(&position));
+            occupancy.add(&position, Layer::Item);
+        }
+        assert_eq!(occupancy.free_count(), 0);
+        assert_consistent(&occupancy);
+    }
+}
@@ -1,14 +1,26 @@
 use ::rand::Rng;
-use ::rand::thread_rng;
-use std::collections::VecDeque;
 use crate::game::game_state::{Position, PowerUp, PowerUpType};
-use crate::utils::{GRID_WIDTH, GRID_HEIGHT};
+use crate::game::occupancy::Occupancy;
+use crate::game::simulation::TICKS_PER_SECOND;
+
+// โอกาส 1% ต่อรอบที่งูขยับ
+pub const DEFAULT_SPAWN_CHANCE: f32 = 0.01;
+// Power-up มีผลนานเท่านี้ตามเวลาจริง ไม่ขึ้นกับความเร็วงูหรือ FPS
+pub const POWER_UP_DURATION_SECS: u32 = 5;
 
 pub struct PowerUpManager {
     pub power_ups: Vec<PowerUp>,
     pub active_power_ups: Vec<(PowerUpType, u32)>,
     pub speed_multiplier: f32,
     pub ghost_mode: bool,
+    // โอกาสเกิด Power-up ใหม่ในแต่ละรอบ (0 = ปิด)
+    pub spawn_chance: f32,
+}
+
+impl Default for PowerUpManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PowerUpManager {
@@ -18,6 +30,7 @@ impl PowerUpManager {
             active_power_ups: Vec::new(),
             speed_multiplier: 1.0,
             ghost_mode: false,
+            spawn_chance: DEFAULT_SPAWN_CHANCE,
         }
     }
 
@@ -28,27 +41,22 @@ impl PowerUpManager {
         self.ghost_mode = false;
     }
 
-    pub fn random_power_up(snake: &VecDeque<Position>, food: &Position) -> PowerUp {
-        let mut rng = thread_rng();
+    // คืนค่า None ถ้าไม่มีช่องว่างเหลือ
+    pub fn random_power_up(occupancy: &Occupancy, rng: &mut impl Rng) -> Option<PowerUp> {
         let power_types = [PowerUpType::SpeedBoost, PowerUpType::Shrink, PowerUpType::GhostMode];
         let power_type = power_types[rng.gen_range(0..power_types.len())];
-        
-        loop {
-            let pos = Position {
-                x: rng.gen_range(0..GRID_WIDTH),
-                y: rng.gen_range(0..GRID_HEIGHT),
-            };
-            if !snake.contains(&pos) && pos != *food {
-                return PowerUp {
-                    position: pos,
-                    power_type,
-                    duration: 300, // 5 seconds at 60 FPS
-                };
-            }
-        }
+
+        occupancy.random_free(rng).map(|position| PowerUp {
+            position,
+            power_type,
+            duration: POWER_UP_DURATION_SECS * TICKS_PER_SECOND as u32,
+        })
     }
 
-    pub fn update(&mut self, snake: &VecDeque<Position>, food: &Position) {
+    // นับเวลาของ Power-up ที่ทำงานอยู่ เรียกทุก tick คืนค่า Power-up ที่หมดเวลา
+    pub fn tick(&mut self) -> Vec<PowerUpType> {
+        let mut expired = Vec::new();
+
         // อัปเดต active power-ups
         self.active_power_ups.retain_mut(|(power_type, duration)| {
             if *duration > 0 {
@@ -63,16 +71,24 @@ impl PowerUpManager {
                         // Shrink effect จะหายไปอัตโนมัติ
                     }
                 }
+                expired.push(*power_type);
                 false
             } else {
                 true
             }
         });
 
-        // สร้าง Power-up ใหม่ (โอกาส 1% ต่อเฟรม)
-        if self.power_ups.is_empty() && thread_rng().gen::<f32>() < 0.01 {
-            self.power_ups.push(Self::random_power_up(snake, food));
+        expired
+    }
+
+    // สุ่มเกิด Power-up ใหม่ตาม spawn_chance เรียกทุกรอบที่งูขยับ
+    pub fn try_spawn(&mut self, occupancy: &Occupancy, rng: &mut impl Rng) -> Option<PowerUp> {
+        if self.power_ups.is_empty() && self.spawn_chance > 0.0 && rng.gen::<f32>() < self.spawn_chance {
+            let power_up = Self::random_power_up(occupancy, rng)?;
+            self.power_ups.push(power_up);
+            return Some(power_up);
         }
+        None
     }
 
     pub fn check_collision(&mut self, position: &Position) -> Option<PowerUpType> {
@@ -0,0 +1,290 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use crate::game::board::Board;
+use crate::game::config::{parse_speed_thresholds, BoundaryRule, GameConfig};
+use crate::game::game_state::{Direction, GameMode};
+use crate::game::level::Level;
+use crate::utils::write_atomic;
+
+// รุ่น 2: Power-up นับเวลาเป็น tick ไฟล์รุ่น 1 จึงเล่นซ้ำได้ไม่ตรง
+const REPLAY_HEADER: &str = "snake-replay 2";
+const OLD_REPLAY_HEADER: &str = "snake-replay 1";
+
+// บันทึกเฉพาะ seed, การตั้งค่าเกม และการเปลี่ยนทิศทางในแต่ละ tick
+// เพราะ Simulation ทำงานแบบ deterministic เท่านี้ก็เล่นซ้ำได้ครบ
+#[derive(Clone, Debug)]
+pub struct Replay {
+    pub seed: u64,
+    pub config: GameConfig,
+    // ไฟล์เก็บแค่ชื่อด่าน ต้องเรียก attach_level ก่อนเล่นซ้ำ
+    pub level_id: Option<String>,
+    pub inputs: Vec<(u64, Direction)>,
+}
+
+impl Replay {
+    pub fn new(seed: u64, config: GameConfig) -> Self {
+        Replay {
+            seed,
+            level_id: config.level.as_ref().map(|level| level.id.clone()),
+            config,
+            inputs: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, tick: u64, direction: Direction) {
+        self.inputs.push((tick, direction));
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "{}\nseed {}\nmode {}\nboard {}\nboundary {}\n",
+            REPLAY_HEADER,
+            self.seed,
+            self.config.game_mode.name(),
+            self.config.board.to_text(),
+            self.config.boundary.name()
+        );
+        if let Some(level_id) = &self.level_id {
+            text.push_str(&format!("level {}\n", level_id));
+        }
+        if let Some(length) = self.config.maze_regen_length {
+            text.push_str(&format!("maze_regen {}\n", length));
+        }
+        if let Some(chance) = self.config.power_up_chance {
+            text.push_str(&format!("power_up_chance {}\n", chance));
+        }
+        if !self.config.speed_thresholds.is_empty() {
+            let thresholds: Vec<String> = self.config.speed_thresholds.iter().map(|t| t.to_string()).collect();
+            text.push_str(&format!("speed_thresholds {}\n", thresholds.join(",")));
+        }
+        for (tick, direction) in &self.inputs {
+            text.push_str(&format!("{} {}\n", tick, direction.to_char()));
+        }
+        text
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+
+        match lines.next() {
+            Some((_, line)) if line.trim() == REPLAY_HEADER => {}
+            Some((_, line)) if line.trim() == OLD_REPLAY_HEADER => {
+                return Err("replay was recorded with an older version of the game".to_string());
+            }
+            _ => return Err("not a snake replay file".to_string()),
+        }
+
+        let mut seed = None;
+        let mut game_mode = None;
+        let mut board = Board::default();
+        let mut boundary = BoundaryRule::Wrap;
+        let mut level_id = None;
+        let mut maze_regen_length = None;
+        // ไม่มีบรรทัดนี้ = ใช้ค่ามาตรฐาน
+        let mut power_up_chance = None;
+        let mut speed_thresholds = Vec::new();
+        let mut inputs = Vec::new();
+
+        for (index, line) in lines {
+            let line_number = index + 1;
+            let mut parts = line.split_whitespace();
+            let (key, value) = match (parts.next(), parts.next()) {
+                (Some(key), Some(value)) => (key, value),
+                _ => return Err(format!("line {}: expected two fields", line_number)),
+            };
+
+            match key {
+                "seed" => {
+                    seed = Some(value.parse().map_err(|_| format!("line {}: invalid seed", line_number))?);
+                }
+                "mode" => {
+                    game_mode = Some(
+                        GameMode::from_name(value)
+                            .ok_or_else(|| format!("line {}: unknown mode '{}'", line_number, value))?,
+                    );
+                }
+                "board" => {
+                    board = Board::from_text(value)
+                        .ok_or_else(|| format!("line {}: invalid board size '{}'", line_number, value))?;
+                }
+                "boundary" => {
+                    boundary = BoundaryRule::from_name(value)
+                        .ok_or_else(|| format!("line {}: unknown boundary '{}'", line_number, value))?;
+                }
+                "level" => level_id = Some(value.to_string()),
+                "maze_regen" => {
+                    maze_regen_length = Some(
+                        value
+                            .parse()
+                            .map_err(|_| format!("line {}: invalid maze_regen length", line_number))?,
+                    );
+                }
+                "power_up_chance" => {
+                    power_up_chance = Some(
+                        value
+                            .parse()
+                            .map_err(|_| format!("line {}: invalid power_up_chance", line_number))?,
+                    );
+                }
+                "speed_thresholds" => {
+                    speed_thresholds = parse_speed_thresholds(value)
+                        .ok_or_else(|| format!("line {}: invalid speed_thresholds '{}'", line_number, value))?;
+                }
+                tick => {
+                    let tick: u64 = tick.parse().map_err(|_| format!("line {}: invalid tick", line_number))?;
+                    let direction = value
+                        .chars()
+                        .next()
+                        .and_then(Direction::from_char)
+                        .ok_or_else(|| format!("line {}: invalid direction '{}'", line_number, value))?;
+                    if inputs.last().is_some_and(|(last, _)| *last > tick) {
+                        return Err(format!("line {}: ticks must be in order", line_number));
+                    }
+                    inputs.push((tick, direction));
+                }
+            }
+        }
+
+        let mut config = GameConfig::new(game_mode.ok_or("missing mode")?, board, boundary);
+        config.maze_regen_length = maze_regen_length;
+        config.power_up_chance = power_up_chance;
+        config.speed_thresholds = speed_thresholds;
+        Ok(Replay {
+            seed: seed.ok_or("missing seed")?,
+            config,
+            level_id,
+            inputs,
+        })
+    }
+
+    // หาด่านตามชื่อที่บันทึกไว้ แล้วใส่กลับเข้าไปใน config
+    pub fn attach_level(&mut self, levels: &[Arc<Level>]) -> Result<(), String> {
+        if let Some(level_id) = &self.level_id {
+            let level = levels
+                .iter()
+                .find(|level| &level.id == level_id)
+                .ok_or_else(|| format!("level '{}' not found", level_id))?;
+            self.config.level = Some(Arc::clone(level));
+        }
+        Ok(())
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        write_atomic(path, &self.to_text())
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::from_text(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+// ป้อน input จาก Replay ให้ Simulation ทีละ tick
+pub struct ReplayPlayer {
+    pub replay: Replay,
+    next_input: usize,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> Self {
+        ReplayPlayer {
+            replay,
+            next_input: 0,
+        }
+    }
+
+    pub fn input_for_tick(&mut self, tick: u64) -> Option<Direction> {
+        let mut direction = None;
+        while let Some(&(input_tick, input_direction)) = self.replay.inputs.get(self.next_input) {
+            if input_tick > tick {
+                break;
+            }
+            if input_tick == tick {
+                direction = Some(input_direction);
+            }
+            self.next_input += 1;
+        }
+        direction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Replay {
+        let mut config = GameConfig::new(GameMode::Maze, Board::new(24, 18), BoundaryRule::Bounce);
+        config.maze_regen_length = Some(12);
+        config.power_up_chance = Some(0.25);
+        config.speed_thresholds = vec![5, 15, 30];
+        let mut replay = Replay::new(42, config);
+        replay.record(3, Direction::Up);
+        replay.record(3, Direction::Left);
+        replay.record(17, Direction::Down);
+        replay
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let replay = sample();
+        let parsed = Replay::from_text(&replay.to_text()).unwrap();
+        assert_eq!(parsed.seed, replay.seed);
+        assert_eq!(parsed.config, replay.config);
+        assert_eq!(parsed.level_id, None);
+        assert_eq!(parsed.inputs, replay.inputs);
+        assert_eq!(parsed.to_text(), replay.to_text());
+    }
+
+    #[test]
+    fn keeps_level_id() {
+        let mut replay = sample();
+        replay.level_id = Some("02_pillars".to_string());
+        let mut parsed = Replay::from_text(&replay.to_text()).unwrap();
+        assert_eq!(parsed.level_id.as_deref(), Some("02_pillars"));
+        assert!(parsed.config.level.is_none());
+        assert_eq!(parsed.attach_level(&[]).unwrap_err(), "level '02_pillars' not found");
+    }
+
+    #[test]
+    fn rejects_old_and_foreign_files() {
+        let old = format!("{}\nseed 1\nmode normal\n", OLD_REPLAY_HEADER);
+        assert_eq!(
+            Replay::from_text(&old).unwrap_err(),
+            "replay was recorded with an older version of the game"
+        );
+        assert_eq!(Replay::from_text("").unwrap_err(), "not a snake replay file");
+        assert_eq!(Replay::from_text("seed 1\nmode normal\n").unwrap_err(), "not a snake replay file");
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        let cases = [
+            ("seed\n", "line 2: expected two fields"),
+            ("seed x\n", "line 2: invalid seed"),
+            ("seed 1\nmode snail\n", "line 3: unknown mode 'snail'"),
+            ("seed 1\nmode normal\nboard 0x\n", "line 4: invalid board size '0x'"),
+            ("seed 1\nmode normal\nboundary soft\n", "line 4: unknown boundary 'soft'"),
+            ("seed 1\nmode normal\nspeed_thresholds 5,x\n", "line 4: invalid speed_thresholds '5,x'"),
+            ("seed 1\nmode normal\n5 Q\n", "line 4: invalid direction 'Q'"),
+            ("seed 1\nmode normal\n9 U\n\n4 D\n", "line 6: ticks must be in order"),
+            ("seed 1\n", "missing mode"),
+            ("mode normal\n", "missing seed"),
+        ];
+        for (body, message) in cases {
+            let text = format!("{}\n{}", REPLAY_HEADER, body);
+            assert_eq!(Replay::from_text(&text).unwrap_err(), message, "{}", body);
+        }
+    }
+
+    #[test]
+    fn player_feeds_inputs_on_their_ticks() {
+        let mut player = ReplayPlayer::new(sample());
+        assert_eq!(player.input_for_tick(1), None);
+        // สองทิศใน tick เดียวกันใช้ทิศหลังสุด
+        assert_eq!(player.input_for_tick(3), Some(Direction::Left));
+        assert_eq!(player.input_for_tick(16), None);
+        assert_eq!(player.input_for_tick(17), Some(Direction::Down));
+    }
+}
@@ -0,0 +1,687 @@
+use ::rand::rngs::StdRng;
+use ::rand::seq::SliceRandom;
+use ::rand::SeedableRng;
+use std::collections::VecDeque;
+use crate::game::config::{BoundaryRule, GameConfig};
+use crate::game::food::Food;
+use crate::game::game_state::{Direction, GameMode, Position, PowerUpType};
+use crate::game::level::LevelGoal;
+use crate::game::grid::Grid;
+use crate::game::hazards::Hazard;
+use crate::game::occupancy::{Layer, Occupancy};
+use crate::game::obstacles::{generate_maze, generate_obstacles, ObstaclePattern, MAZE_MODE_OPENING_CHANCE};
+use crate::game::power_ups::{PowerUpManager, DEFAULT_SPAWN_CHANCE};
+use crate::game::replay::Replay;
+use crate::game::snake::Snake;
+
+// เกมเดินที่ 60 tick ต่อวินาทีตามเวลาจริง ไม่ขึ้นกับ FPS ของจอ
+pub const TICKS_PER_SECOND: u64 = 60;
+// ความเร็วงูปกติ (ช่องต่อวินาที) ก่อนคูณ speed_multiplier
+pub const BASE_MOVES_PER_SECOND: f32 = 6.0;
+// ความเร็วที่เพิ่มขึ้น (ช่องต่อวินาที) ต่อหนึ่งขั้นของ speed_thresholds
+pub const SPEED_STEP: f32 = 1.0;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GameEvent {
+    Moved,
+    AteFood,
+    PowerUpSpawned(PowerUpType),
+    PowerUpCollected(PowerUpType),
+    PowerUpExpired(PowerUpType),
+    Bounced,
+    Died(DeathCause),
+    LevelComplete,
+    MazeRegenerated,
+    // ไม่มีช่องว่างให้อาหารเกิดอีกแล้ว = ชนะ
+    BoardFilled,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DeathCause {
+    SelfCollision,
+    Obstacle,
+    Wall,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SimulationState {
+    Running,
+    GameOver,
+    // ทำเป้าหมายของด่าน Campaign สำเร็จ
+    LevelComplete,
+    // งูยาวเต็มกระดาน
+    Won,
+}
+
+// กติกาเกมทั้งหมด แยกออกจากการวาดภาพ (ไม่เรียก macroquad เลย)
+pub struct Simulation {
+    pub snake: Snake,
+    // ตัวงูก่อนขยับครั้งล่าสุด ใช้วาดงูเลื่อนแบบนุ่มระหว่างช่อง
+    pub previous_body: VecDeque<Position>,
+    pub food: Food,
+    pub power_up_manager: PowerUpManager,
+    pub obstacles: Vec<Position>,
+    // ของทุกอย่างบนกระดานแยกตามช่อง ตรวจการชนและสุ่มช่องว่างได้ O(1)
+    pub occupancy: Occupancy,
+    // สิ่งกีดขวางเคลื่อนที่/ตั้งเวลาจากไฟล์ด่าน
+    pub hazards: Vec<Hazard>,
+    // รูปแบบที่สุ่มได้ตอนเริ่มเกมในโหมด Obstacle (None = ไม่ได้สุ่ม)
+    pub obstacle_pattern: Option<ObstaclePattern>,
+    pub config: GameConfig,
+    pub state: SimulationState,
+    pub death_cause: Option<DeathCause>,
+    // จำนวน tick ตั้งแต่งูขยับครั้งล่าสุด
+    pub ticks_since_move: u32,
+    pub tick: u64,
+    pub power_ups_collected: u32,
+    pub food_eaten: u32,
+    pub seed: u64,
+    pub recording: Replay,
+    // ทุกการสุ่มในเกมต้องผ่าน rng ตัวนี้ เพื่อให้เล่นซ้ำได้จาก seed เดียวกัน
+    rng: StdRng,
+}
+
+impl Simulation {
+    pub fn new(config: GameConfig, seed: u64) -> Self {
+        let rng = StdRng::seed_from_u64(seed);
+        let mut simulation = Simulation {
+            snake: Snake::new(&config.board),
+            previous_body: VecDeque::new(),
+            food: Food::new(config.board.center()),
+            power_up_manager: PowerUpManager::new(),
+            obstacles: Vec::new(),
+            occupancy: Occupancy::new(config.board),
+            hazards: Vec::new(),
+            obstacle_pattern: None,
+            config: config.clone(),
+            state: SimulationState::Running,
+            death_cause: None,
+            ticks_since_move: 0,
+            tick: 0,
+            power_ups_collected: 0,
+            food_eaten: 0,
+            seed,
+            recording: Replay::new(seed, config),
+            rng,
+        };
+        simulation.reset();
+        simulation
+    }
+
+    pub fn reset(&mut self) {
+        self.rng = StdRng::seed_from_u64(self.seed);
+        self.state = SimulationState::Running;
+        self.death_cause = None;
+        self.ticks_since_move = 0;
+        self.tick = 0;
+        self.power_ups_collected = 0;
+        self.food_eaten = 0;
+        self.recording = Replay::new(self.seed, self.config.clone());
+        self.power_up_manager.reset();
+        self.obstacle_pattern = None;
+        self.hazards.clear();
+
+        if let Some(level) = self.config.level.clone() {
+            self.snake.reset_at(level.spawn, level.direction);
+            self.previous_body = self.snake.body.clone();
+            self.hazards = level.hazards.iter().cloned().map(Hazard::new).collect();
+            self.set_obstacles(level.walls.clone());
+            self.power_up_manager.spawn_chance = if level.power_ups { self.power_up_chance() } else { 0.0 };
+            self.respawn_food();
+            return;
+        }
+
+        self.snake.reset(&self.config.board);
+        self.previous_body = self.snake.body.clone();
+        self.power_up_manager.spawn_chance = self.power_up_chance();
+        match self.config.game_mode {
+            GameMode::Obstacle => {
+                let pattern = *ObstaclePattern::ALL.choose(&mut self.rng).unwrap_or(&ObstaclePattern::Clusters);
+                let obstacles = generate_obstacles(
+                    &self.config.board,
+                    self.config.boundary,
+                    self.snake.body[0],
+                    self.snake.direction,
+                    pattern,
+                    &mut self.rng,
+                );
+                self.set_obstacles(obstacles);
+                self.obstacle_pattern = Some(pattern);
+            }
+            GameMode::Maze => self.regenerate_maze(),
+            _ => self.set_obstacles(Vec::new()),
+        }
+        self.respawn_food();
+    }
+
+    fn set_obstacles(&mut self, obstacles: Vec<Position>) {
+        self.obstacles = obstacles;
+        self.rebuild_occupancy();
+    }
+
+    // สร้าง occupancy ใหม่ทั้งหมด ใช้ตอนเปลี่ยนกระดานทั้งแผ่น ระหว่างเล่นจะอัปเดตทีละช่อง
+    fn rebuild_occupancy(&mut self) {
+        let mut occupancy = Occupancy::new(self.config.board);
+        for hazard in &self.hazards {
+            // เส้นทางของ hazard ห้ามเกิดอาหาร/Power-up
+            for cell in hazard.spec.zone() {
+                occupancy.reserve(cell);
+            }
+            for cell in hazard.solid_cells() {
+                occupancy.add(&cell, Layer::Obstacle);
+            }
+        }
+        for obstacle in &self.obstacles {
+            occupancy.add(obstacle, Layer::Obstacle);
+        }
+        for segment in &self.snake.body {
+            occupancy.add(segment, Layer::Snake);
+        }
+        occupancy.add(&self.food.position, Layer::Item);
+        for power_up in &self.power_up_manager.power_ups {
+            occupancy.add(&power_up.position, Layer::Item);
+        }
+        self.occupancy = occupancy;
+    }
+
+    pub fn is_obstacle(&self, position: &Position) -> bool {
+        self.occupancy.has_obstacle(position)
+    }
+
+    fn grow_snake(&mut self, head: Position) {
+        self.snake.grow(head);
+        self.occupancy.add(&head, Layer::Snake);
+    }
+
+    fn shrink_snake(&mut self) {
+        if let Some(tail) = self.snake.shrink() {
+            self.occupancy.remove(&tail, Layer::Snake);
+        }
+    }
+
+    // สร้างเขาวงกตใหม่รอบตัวงู ทุกช่องว่างเดินถึงได้จากหัวงู อาหารจึงไม่มีทางเกิดในที่ปิดตาย
+    fn regenerate_maze(&mut self) {
+        let snake: Vec<Position> = self.snake.body.iter().copied().collect();
+        let maze = generate_maze(
+            &self.config.board,
+            self.config.boundary,
+            &snake,
+            self.snake.direction,
+            MAZE_MODE_OPENING_CHANCE,
+            &mut self.rng,
+        );
+        let walls = Grid::from_positions(self.config.board, &maze);
+        self.power_up_manager.power_ups.retain(|power_up| !walls.get(&power_up.position));
+        self.set_obstacles(maze);
+    }
+
+    // คืนค่า false ถ้าไม่มีช่องว่างเหลือให้อาหารเกิด
+    fn respawn_food(&mut self) -> bool {
+        self.occupancy.remove(&self.food.position, Layer::Item);
+        let mut spawned = self.place_food();
+        if !spawned && !self.power_up_manager.power_ups.is_empty() {
+            // ช่องว่างที่เหลือมีแต่ Power-up วางอยู่ เก็บ Power-up ออกให้อาหารเกิดแทน
+            for power_up in self.power_up_manager.power_ups.drain(..) {
+                self.occupancy.remove(&power_up.position, Layer::Item);
+            }
+            spawned = self.place_food();
+        }
+        if spawned {
+            self.occupancy.add(&self.food.position, Layer::Item);
+        }
+        spawned
+    }
+
+    fn place_food(&mut self) -> bool {
+        match &self.config.level {
+            Some(level) if !level.food_spots.is_empty() => {
+                self.food.respawn_at_spots(&level.food_spots, &self.occupancy, &mut self.rng)
+            }
+            _ => self.food.respawn(&self.occupancy, &mut self.rng),
+        }
+    }
+
+    fn power_up_chance(&self) -> f32 {
+        self.config.power_up_chance.unwrap_or(DEFAULT_SPAWN_CHANCE)
+    }
+
+    // ความเร็วก่อนคูณ Power-up เพิ่มขึ้นทีละขั้นตามจำนวนอาหารที่กินไป
+    pub fn base_moves_per_second(&self) -> f32 {
+        let steps = self
+            .config
+            .speed_thresholds
+            .iter()
+            .filter(|threshold| self.food_eaten >= **threshold)
+            .count();
+        BASE_MOVES_PER_SECOND + SPEED_STEP * steps as f32
+    }
+
+    // จำนวน tick ต่อการขยับหนึ่งช่องที่ความเร็วปัจจุบัน
+    pub fn ticks_per_move(&self) -> u32 {
+        let moves_per_second = self.base_moves_per_second() * self.power_up_manager.speed_multiplier;
+        ((TICKS_PER_SECOND as f32 / moves_per_second).round() as u32).max(1)
+    }
+
+    // step ครั้งถัดไปงูจะขยับหรือไม่ ใช้ให้ AI คิดทางเฉพาะตอนที่จำเป็น
+    pub fn moves_next_step(&self) -> bool {
+        self.state == SimulationState::Running && self.ticks_since_move + 1 >= self.ticks_per_move()
+    }
+
+    // ระยะทางที่งูเดินไปแล้วระหว่างการขยับครั้งก่อนกับครั้งถัดไป (0..=1)
+    // tick_fraction คือเศษของ tick ที่ยังไม่ได้เดิน
+    pub fn move_progress(&self, tick_fraction: f32) -> f32 {
+        if self.state != SimulationState::Running {
+            return 1.0;
+        }
+        ((self.ticks_since_move as f32 + tick_fraction) / self.ticks_per_move() as f32).min(1.0)
+    }
+
+    // ตำแหน่งแต่ละปล้องระหว่าง previous_body กับตัวงูปัจจุบัน หน่วยเป็นช่อง
+    // ปล้องที่ทะลุขอบจะได้ค่านอกกระดาน (เช่น -0.3) ให้ฝั่งวาดวาดซ้ำอีกด้านเอง
+    pub fn interpolated_body(&self, progress: f32) -> Vec<(f32, f32)> {
+        let board = self.config.board;
+        let wraps = self.config.boundary == BoundaryRule::Wrap;
+        self.snake
+            .body
+            .iter()
+            .enumerate()
+            .map(|(i, current)| {
+                let previous = self.previous_body.get(i).copied().unwrap_or(*current);
+                let mut dx = current.x - previous.x;
+                let mut dy = current.y - previous.y;
+                if wraps && dx.abs() == board.width - 1 {
+                    dx = -dx.signum();
+                }
+                if wraps && dy.abs() == board.height - 1 {
+                    dy = -dy.signum();
+                }
+                // ไม่ใช่ช่องติดกัน (เช่นงูกลับหัว) ให้วาดที่ตำแหน่งปัจจุบันเลย
+                if dx.abs() + dy.abs() != 1 {
+                    return (current.x as f32, current.y as f32);
+                }
+                let remaining = 1.0 - progress;
+                (current.x as f32 - dx as f32 * remaining, current.y as f32 - dy as f32 * remaining)
+            })
+            .collect()
+    }
+
+    pub fn score(&self) -> usize {
+        self.snake.len() - 1
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.state != SimulationState::Running
+    }
+
+    pub fn goal(&self) -> Option<LevelGoal> {
+        self.config.level.as_ref().and_then(|level| level.goal)
+    }
+
+    // ความคืบหน้าของเป้าหมายด่าน นับหน่วยเดียวกับ LevelGoal::target
+    pub fn goal_progress(&self) -> Option<u64> {
+        self.goal().map(|goal| match goal {
+            LevelGoal::Length(_) => self.snake.len() as u64,
+            LevelGoal::Food(_) => self.food_eaten as u64,
+            LevelGoal::Survive(_) => self.tick / TICKS_PER_SECOND,
+        })
+    }
+
+    fn goal_met(&self) -> bool {
+        match (self.goal(), self.goal_progress()) {
+            (Some(goal), Some(progress)) => progress >= goal.target(),
+            _ => false,
+        }
+    }
+
+    fn die(&mut self, cause: DeathCause, events: &mut Vec<GameEvent>) {
+        self.state = SimulationState::GameOver;
+        self.death_cause = Some(cause);
+        events.push(GameEvent::Died(cause));
+    }
+
+    // หาตำแหน่งหัวถัดไปตามกติกาขอบกระดาน คืนค่า None ถ้าชนกำแพง
+    fn next_head(&mut self, events: &mut Vec<GameEvent>) -> Option<Position> {
+        let board = self.config.board;
+        match self.config.boundary {
+            BoundaryRule::Wrap => Some(self.snake.move_snake(&board)),
+            BoundaryRule::Solid => Some(self.snake.next_head()).filter(|head| board.contains(head)),
+            BoundaryRule::Bounce => {
+                let head = self.snake.next_head();
+                if board.contains(&head) {
+                    return Some(head);
+                }
+                self.snake.reverse();
+                events.push(GameEvent::Bounced);
+                Some(self.snake.next_head()).filter(|head| board.contains(head))
+            }
+        }
+    }
+
+    pub fn step(&mut self, input: Option<Direction>) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        if self.state != SimulationState::Running {
+            return events;
+        }
+
+        if let Some(direction) = input {
+            let previous = self.snake.direction;
+            self.snake.change_direction(direction);
+            if self.snake.direction != previous {
+                self.recording.record(self.tick, direction);
+            }
+        }
+
+        self.tick += 1;
+        self.ticks_since_move += 1;
+
+        for power_type in self.power_up_manager.tick() {
+            events.push(GameEvent::PowerUpExpired(power_type));
+        }
+
+        for hazard in &mut self.hazards {
+            let before = hazard.solid_cells();
+            let occupancy = &self.occupancy;
+            hazard.tick(|position| occupancy.has_snake(position));
+            let after = hazard.solid_cells();
+            if before != after {
+                for cell in &before {
+                    self.occupancy.remove(cell, Layer::Obstacle);
+                }
+                for cell in &after {
+                    self.occupancy.add(cell, Layer::Obstacle);
+                }
+            }
+        }
+
+        if self.ticks_since_move < self.ticks_per_move() {
+            return events;
+        }
+        self.ticks_since_move = 0;
+        self.previous_body = self.snake.body.clone();
+
+        if let Some(power_up) = self.power_up_manager.try_spawn(&self.occupancy, &mut self.rng) {
+            self.occupancy.add(&power_up.position, Layer::Item);
+            events.push(GameEvent::PowerUpSpawned(power_up.power_type));
+        }
+
+        let new_head = match self.next_head(&mut events) {
+            Some(head) => head,
+            None => {
+                self.die(DeathCause::Wall, &mut events);
+                return events;
+            }
+        };
+
+        if !self.power_up_manager.ghost_mode {
+            if self.occupancy.has_snake(&new_head) {
+                self.die(DeathCause::SelfCollision, &mut events);
+                return events;
+            }
+            if self.is_obstacle(&new_head) {
+                self.die(DeathCause::Obstacle, &mut events);
+                return events;
+            }
+        }
+
+        self.grow_snake(new_head);
+        events.push(GameEvent::Moved);
+
+        // ตรวจสอบการชนกับ Power-up
+        if let Some(power_type) = self.power_up_manager.check_collision(&new_head) {
+            self.occupancy.remove(&new_head, Layer::Item);
+            if power_type == PowerUpType::Shrink {
+                for _ in 0..2 {
+                    self.shrink_snake();
+                }
+            }
+            self.power_ups_collected += 1;
+            events.push(GameEvent::PowerUpCollected(power_type));
+        }
+
+        if new_head == self.food.position {
+            self.food_eaten += 1;
+            events.push(GameEvent::AteFood);
+            if !self.respawn_food() {
+                self.state = SimulationState::Won;
+                events.push(GameEvent::BoardFilled);
+                return events;
+            }
+        } else {
+            self.shrink_snake();
+        }
+
+        let regen_due = self
+            .config
+            .maze_regen_length
+            .is_some_and(|length| length > 0 && self.snake.len().is_multiple_of(length));
+        if self.config.game_mode == GameMode::Maze && events.contains(&GameEvent::AteFood) && regen_due {
+            self.regenerate_maze();
+            if self.is_obstacle(&self.food.position) && !self.respawn_food() {
+                self.state = SimulationState::Won;
+                events.push(GameEvent::BoardFilled);
+                return events;
+            }
+            events.push(GameEvent::MazeRegenerated);
+        }
+
+        if self.goal_met() {
+            self.state = SimulationState::LevelComplete;
+            events.push(GameEvent::LevelComplete);
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::board::Board;
+
+    fn config(board: Board, boundary: BoundaryRule) -> GameConfig {
+        let mut config = GameConfig::new(GameMode::Normal, board, boundary);
+        config.power_up_chance = Some(0.0);
+        config
+    }
+
+    // เดินจนงูขยับหนึ่งช่อง ส่งทิศไปเฉพาะ tick ที่ขยับ
+    fn move_once(sim: &mut Simulation, direction: Option<Direction>) -> Vec<GameEvent> {
+        loop {
+            let moves = sim.moves_next_step();
+            let events = sim.step(if moves { direction } else { None });
+            if moves || sim.is_over() {
+                return events;
+            }
+        }
+    }
+
+    fn place_food(sim: &mut Simulation, position: Position) {
+        sim.occupancy.remove(&sim.food.position, Layer::Item);
+        sim.food.position = position;
+        sim.occupancy.add(&position, Layer::Item);
+    }
+
+    fn head(sim: &Simulation) -> Position {
+        sim.snake.body[0]
+    }
+
+    #[test]
+    fn same_seed_plays_the_same_game() {
+        let inputs = [Direction::Up, Direction::Left, Direction::Down, Direction::Right];
+        let play = |seed: u64| {
+            let mut sim = Simulation::new(GameConfig::new(GameMode::Obstacle, Board::SMALL, BoundaryRule::Wrap), seed);
+            let mut events = Vec::new();
+            for tick in 0..3_000u64 {
+                let input = (tick % 37 == 0).then(|| inputs[(tick / 37) as usize % inputs.len()]);
+                events.extend(sim.step(input));
+            }
+            (events, sim.snake.body.clone(), sim.food.position, sim.obstacles.clone(), sim.tick)
+        };
+        assert_eq!(play(3), play(3));
+        assert_ne!(play(3).3, play(4).3);
+    }
+
+    #[test]
+    fn eating_grows_the_snake_and_respawns_food() {
+        let mut sim = Simulation::new(config(Board::new(10, 10), BoundaryRule::Wrap), 1);
+        let start = head(&sim);
+        place_food(&mut sim, Direction::Right.offset(start));
+
+        let events = move_once(&mut sim, None);
+        assert!(events.contains(&GameEvent::AteFood));
+        assert_eq!(sim.snake.len(), 2);
+        assert_eq!((sim.food_eaten, sim.score()), (1, 1));
+        assert!(!sim.snake.body.contains(&sim.food.position));
+        assert!(sim.occupancy.has_snake(&start));
+
+        // ไม่ได้กิน หางหดตาม ความยาวเท่าเดิม
+        place_food(&mut sim, Position { x: 0, y: 0 });
+        move_once(&mut sim, Some(Direction::Down));
+        assert_eq!(sim.snake.len(), 2);
+        assert!(!sim.occupancy.has_snake(&start));
+    }
+
+    #[test]
+    fn running_into_own_body_ends_the_game() {
+        let mut sim = Simulation::new(config(Board::new(12, 12), BoundaryRule::Wrap), 2);
+        for _ in 0..4 {
+            let ahead = Direction::Right.offset(head(&sim));
+            place_food(&mut sim, ahead);
+            move_once(&mut sim, None);
+        }
+        place_food(&mut sim, Position { x: 0, y: 0 });
+        assert_eq!(sim.snake.len(), 5);
+
+        move_once(&mut sim, Some(Direction::Up));
+        move_once(&mut sim, Some(Direction::Left));
+        let events = move_once(&mut sim, Some(Direction::Down));
+        assert!(events.contains(&GameEvent::Died(DeathCause::SelfCollision)));
+        assert_eq!(sim.state, SimulationState::GameOver);
+        assert!(sim.step(Some(Direction::Right)).is_empty());
+    }
+
+    // กระดาน 5x5 งูเกิดที่ (2,2) หันขวา ขยับสามครั้งจะออกขอบขวา
+    fn run_to_edge(boundary: BoundaryRule) -> (Simulation, Vec<GameEvent>) {
+        let mut sim = Simulation::new(config(Board::new(5, 5), boundary), 0);
+        place_food(&mut sim, Position { x: 2, y: 4 });
+        let mut events = Vec::new();
+        for _ in 0..3 {
+            events.extend(move_once(&mut sim, None));
+        }
+        (sim, events)
+    }
+
+    #[test]
+    fn solid_edge_kills() {
+        let (sim, events) = run_to_edge(BoundaryRule::Solid);
+        assert!(events.contains(&GameEvent::Died(DeathCause::Wall)));
+        assert_eq!(sim.death_cause, Some(DeathCause::Wall));
+        assert_eq!(head(&sim), Position { x: 4, y: 2 });
+    }
+
+    #[test]
+    fn wrap_edge_comes_back_on_the_other_side() {
+        let (sim, events) = run_to_edge(BoundaryRule::Wrap);
+        assert!(!sim.is_over());
+        assert!(!events.contains(&GameEvent::Bounced));
+        assert_eq!(head(&sim), Position { x: 0, y: 2 });
+    }
+
+    #[test]
+    fn bounce_edge_turns_the_snake_around() {
+        let (sim, events) = run_to_edge(BoundaryRule::Bounce);
+        assert!(!sim.is_over());
+        assert!(events.contains(&GameEvent::Bounced));
+        assert_eq!(head(&sim), Position { x: 3, y: 2 });
+        assert_eq!(sim.snake.direction, Direction::Left);
+    }
+
+    #[test]
+    fn bounce_reverses_a_long_snake() {
+        let mut sim = Simulation::new(config(Board::new(6, 6), BoundaryRule::Bounce), 0);
+        for _ in 0..2 {
+            let ahead = Direction::Right.offset(head(&sim));
+            place_food(&mut sim, ahead);
+            move_once(&mut sim, None);
+        }
+        place_food(&mut sim, Position { x: 0, y: 5 });
+        let tail = *sim.snake.body.back().unwrap();
+        assert_eq!(head(&sim), Position { x: 5, y: 3 });
+
+        // หางกลายเป็นหัวแล้วเดินออกจากลำตัวไปทางซ้าย
+        let events = move_once(&mut sim, None);
+        assert!(events.contains(&GameEvent::Bounced));
+        assert_eq!(head(&sim), Direction::Left.offset(tail));
+        assert_eq!(sim.snake.direction, Direction::Left);
+        assert_eq!(sim.snake.len(), 3);
+    }
+
+    fn set_bodies(sim: &mut Simulation, previous: &[(i32, i32)], current: &[(i32, i32)]) {
+        sim.previous_body = previous.iter().map(|&(x, y)| Position { x, y }).collect();
+        sim.snake.body = current.iter().map(|&(x, y)| Position { x, y }).collect();
+    }
+
+    #[test]
+    fn interpolates_between_moves() {
+        let mut sim = Simulation::new(config(Board::new(10, 10), BoundaryRule::Wrap), 1);
+        set_bodies(&mut sim, &[(5, 5), (4, 5), (4, 4)], &[(5, 6), (5, 5), (4, 5)]);
+        assert_eq!(sim.interpolated_body(0.0), [(5.0, 5.0), (4.0, 5.0), (4.0, 4.0)]);
+        assert_eq!(sim.interpolated_body(0.5), [(5.0, 5.5), (4.5, 5.0), (4.0, 4.5)]);
+        assert_eq!(sim.interpolated_body(1.0), [(5.0, 6.0), (5.0, 5.0), (4.0, 5.0)]);
+
+        // ปล้องที่เพิ่งงอกยังไม่มีตำแหน่งเดิม วาดอยู่กับที่
+        set_bodies(&mut sim, &[(5, 5)], &[(6, 5), (5, 5)]);
+        assert_eq!(sim.interpolated_body(0.5), [(5.5, 5.0), (5.0, 5.0)]);
+    }
+
+    // ข้ามขอบแล้วต้องเลื่อนออกนอกขอบไปทางเดิม ไม่ไถลย้อนผ่านกลางกระดาน
+    #[test]
+    fn interpolates_across_wrapped_edges() {
+        let mut sim = Simulation::new(config(Board::new(10, 8), BoundaryRule::Wrap), 1);
+        set_bodies(&mut sim, &[(9, 3), (8, 3)], &[(0, 3), (9, 3)]);
+        assert_eq!(sim.interpolated_body(0.0), [(-1.0, 3.0), (8.0, 3.0)]);
+        assert_eq!(sim.interpolated_body(0.5), [(-0.5, 3.0), (8.5, 3.0)]);
+        assert_eq!(sim.interpolated_body(1.0), [(0.0, 3.0), (9.0, 3.0)]);
+
+        set_bodies(&mut sim, &[(2, 0)], &[(2, 7)]);
+        assert_eq!(sim.interpolated_body(0.25), [(2.0, 7.75)]);
+
+        // ขอบทึบไม่มีการทะลุ ช่องที่ห่างกันทั้งกระดาน (เช่นงูกลับหัว) วาดที่ตำแหน่งปัจจุบัน
+        let mut sim = Simulation::new(config(Board::new(10, 8), BoundaryRule::Solid), 1);
+        set_bodies(&mut sim, &[(9, 3)], &[(0, 3)]);
+        assert_eq!(sim.interpolated_body(0.5), [(0.0, 3.0)]);
+    }
+
+    // เดินวนรอบกระดาน 3x2 จนตัวงูเต็มทุกช่อง อาหารเกิดไม่ได้อีกแล้วจึงชนะ
+    #[test]
+    fn filling_the_board_wins() {
+        let board = Board { width: 3, height: 2 };
+        let mut sim = Simulation::new(config(board, BoundaryRule::Solid), 3);
+        assert_eq!(head(&sim), Position { x: 1, y: 1 });
+        let route = [
+            ((1, 1), Direction::Right),
+            ((2, 1), Direction::Up),
+            ((2, 0), Direction::Left),
+            ((1, 0), Direction::Left),
+            ((0, 0), Direction::Down),
+            ((0, 1), Direction::Right),
+        ];
+        let mut events = Vec::new();
+        for _ in 0..100 {
+            if sim.is_over() {
+                break;
+            }
+            let position = head(&sim);
+            let direction = route.iter().find(|((x, y), _)| Position { x: *x, y: *y } == position).map(|(_, d)| *d);
+            events = move_once(&mut sim, direction);
+        }
+        assert_eq!(events.last(), Some(&GameEvent::BoardFilled));
+        assert!(events.contains(&GameEvent::AteFood));
+        assert_eq!(sim.state, SimulationState::Won);
+        assert_eq!(sim.snake.len(), board.cell_count());
+        assert_eq!((sim.score(), sim.food_eaten), (5, 5));
+        assert_eq!(sim.death_cause, None);
+        // จบแล้ว step ต่อก็ไม่มีอะไรเปลี่ยน
+        assert!(sim.step(Some(Direction::Down)).is_empty());
+    }
+}
@@ -1,48 +1,49 @@
 use std::collections::VecDeque;
 use crate::game::game_state::{Position, Direction};
-use crate::utils::{GRID_WIDTH, GRID_HEIGHT};
+use crate::game::board::Board;
 
 pub struct Snake {
     pub body: VecDeque<Position>,
     pub direction: Direction,
+    // ทิศที่งูขยับจริงครั้งล่าสุด ใช้กันหันกลับเข้าคอเมื่อกดหลายปุ่มก่อนงูขยับ
+    pub last_moved_direction: Direction,
 }
 
 impl Snake {
-    pub fn new() -> Self {
+    pub fn new(board: &Board) -> Self {
         let mut body = VecDeque::new();
-        body.push_back(Position {
-            x: GRID_WIDTH / 2,
-            y: GRID_HEIGHT / 2,
-        });
+        body.push_back(board.center());
 
         Snake {
             body,
             direction: Direction::Right,
+            last_moved_direction: Direction::Right,
         }
     }
 
-    pub fn reset(&mut self) {
+    pub fn reset(&mut self, board: &Board) {
+        self.reset_at(board.center(), Direction::Right);
+    }
+
+    // เริ่มใหม่ที่จุดเกิดที่กำหนด (ใช้กับด่านที่มีจุด S)
+    pub fn reset_at(&mut self, spawn: Position, direction: Direction) {
         let mut body = VecDeque::new();
-        body.push_back(Position {
-            x: GRID_WIDTH / 2,
-            y: GRID_HEIGHT / 2,
-        });
+        body.push_back(spawn);
 
         self.body = body;
-        self.direction = Direction::Right;
+        self.direction = direction;
+        self.last_moved_direction = direction;
     }
 
+    // ห้ามกลับหลังเทียบกับทิศที่ขยับจริง ไม่ใช่ทิศที่กดค้างไว้
     pub fn change_direction(&mut self, new_dir: Direction) {
-        if (self.direction == Direction::Up && new_dir != Direction::Down)
-            || (self.direction == Direction::Down && new_dir != Direction::Up)
-            || (self.direction == Direction::Left && new_dir != Direction::Right)
-            || (self.direction == Direction::Right && new_dir != Direction::Left)
-        {
+        if new_dir != self.last_moved_direction.opposite() {
             self.direction = new_dir;
         }
     }
 
-    pub fn move_snake(&mut self) -> Position {
+    // ตำแหน่งหัวถัดไปก่อนจัดการขอบกระดาน (อาจอยู่นอกกระดาน)
+    pub fn next_head(&self) -> Position {
         let mut new_head = *self.body.front().unwrap();
         match self.direction {
             Direction::Up => new_head.y -= 1,
@@ -50,38 +51,64 @@ impl Snake {
             Direction::Left => new_head.x -= 1,
             Direction::Right => new_head.x += 1,
         }
+        new_head
+    }
 
+    pub fn move_snake(&mut self, board: &Board) -> Position {
         // เดินทะลุขอบ (wrap around)
-        if new_head.x < 0 {
-            new_head.x = GRID_WIDTH - 1;
-        } else if new_head.x >= GRID_WIDTH {
-            new_head.x = 0;
-        }
-
-        if new_head.y < 0 {
-            new_head.y = GRID_HEIGHT - 1;
-        } else if new_head.y >= GRID_HEIGHT {
-            new_head.y = 0;
-        }
+        board.wrap(self.next_head())
+    }
 
-        new_head
+    // กลับหัวกลับหาง หางกลายเป็นหัวและวิ่งออกจากลำตัว
+    pub fn reverse(&mut self) {
+        let body: Vec<Position> = self.body.iter().rev().copied().collect();
+        self.direction = match body.get(1) {
+            Some(&neck) => Direction::between(neck, body[0]).unwrap_or(self.direction.opposite()),
+            None => self.direction.opposite(),
+        };
+        self.last_moved_direction = self.direction;
+        self.body = body.into_iter().collect();
     }
 
     pub fn grow(&mut self, new_head: Position) {
         self.body.push_front(new_head);
+        self.last_moved_direction = self.direction;
     }
 
-    pub fn shrink(&mut self) {
+    // คืนค่าช่องหางที่หายไป (ถ้ามี)
+    pub fn shrink(&mut self) -> Option<Position> {
         if self.body.len() > 1 {
-            self.body.pop_back();
+            self.body.pop_back()
+        } else {
+            None
         }
     }
 
-    pub fn contains(&self, position: &Position) -> bool {
-        self.body.contains(position)
-    }
-
     pub fn len(&self) -> usize {
         self.body.len()
     }
-} 
\ No newline at end of file
+
+    pub fn is_empty(&self) -> bool {
+        self.body.is_empty()
+    }
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // กดขึ้นแล้วกดซ้ายก่อนงูขยับ ต้องไม่หันกลับเข้าคอ
+    #[test]
+    fn rejects_reversal_into_neck_between_moves() {
+        let mut snake = Snake::new(&Board::new(10, 10));
+        snake.grow(snake.next_head());
+        snake.change_direction(Direction::Up);
+        snake.change_direction(Direction::Left);
+        assert_eq!(snake.direction, Direction::Up);
+
+        snake.grow(snake.next_head());
+        snake.change_direction(Direction::Down);
+        assert_eq!(snake.direction, Direction::Up);
+        snake.change_direction(Direction::Left);
+        assert_eq!(snake.direction, Direction::Left);
+    }
+}
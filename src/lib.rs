@@ -0,0 +1,6 @@
+pub mod ai;
+pub mod audio;
+pub mod controller;
+pub mod game;
+pub mod ui;
+pub mod utils;
@@ -1,207 +1,618 @@
 use macroquad::prelude::*;
-use ::rand::Rng;
-use ::rand::thread_rng;
 
-mod utils;
-mod audio;
-mod ui;
-mod game;
+use snake_gui::ai::{Autopilot, HamiltonianSolver};
+use snake_gui::controller::{self, Controller, KeyboardController};
+#[cfg(feature = "gamepad")]
+use snake_gui::controller::{GamepadController, GilrsSource, MultiController};
+use snake_gui::utils::*;
+use snake_gui::utils::leaderboard::{format_date, unix_timestamp};
+use snake_gui::audio::{MusicManager, MusicTrack, SoundEffect, SoundManager};
+use snake_gui::ui::{Button, Slider, TextInput};
+use snake_gui::game::{Board, BoardPreset, BoundaryRule, DeathCause, GameConfig, HazardKind, HazardPhase, Level, LevelLoadError, Simulation, SimulationState, GameEvent, GameState, GameMode, PowerUpType, Replay, ReplayPlayer, TICKS_PER_SECOND};
+use std::path::Path;
+use std::sync::Arc;
 
-use utils::*;
-use audio::SoundManager;
-use ui::Button;
-use game::{Snake, Food, PowerUpManager, GameState, GameMode, Direction, PowerUpType};
+const LAST_REPLAY_FILE: &str = "replays/last.replay";
+const REPLAY_FAST_FORWARD: u32 = 4;
+// เฟรมที่ค้างนานกว่านี้ (เช่นลากหน้าต่าง) ไม่ต้องเร่งเล่นตามให้ทัน
+const MAX_FRAME_TIME: f32 = 0.25;
+
+const SLIDER_WIDTH: f32 = 300.0;
+const SLIDER_HEIGHT: f32 = 20.0;
+
+const MENU_BUTTON_WIDTH: f32 = 200.0;
+const MENU_BUTTON_HEIGHT: f32 = 40.0;
+
+// ตำแหน่งปุ่มในเมนู วางเป็นสองคอลัมน์ เรียงซ้ายไปขวา บนลงล่างตามลำดับ index
+fn menu_slot(index: usize) -> (f32, f32) {
+    let column = (index % 2) as f32;
+    let row = (index / 2) as f32;
+    (
+        screen_width() / 2.0 - MENU_BUTTON_WIDTH - 10.0 + column * (MENU_BUTTON_WIDTH + 20.0),
+        screen_height() / 2.0 - 130.0 + row * 50.0,
+    )
+}
+
+// ผู้เล่นจริง: คีย์บอร์ด และจอยด้วยถ้าเปิด feature gamepad
+fn human_controller() -> Box<dyn Controller> {
+    #[cfg(feature = "gamepad")]
+    match GilrsSource::new() {
+        Ok(source) => {
+            return Box::new(MultiController::new(vec![
+                Box::new(KeyboardController::new()),
+                Box::new(GamepadController::new(source)),
+            ]));
+        }
+        Err(e) => eprintln!("Gamepad disabled: {}", e),
+    }
+    Box::new(KeyboardController::new())
+}
+
+fn menu_button(index: usize, text: &str) -> Button {
+    let (x, y) = menu_slot(index);
+    Button::new(x, y, MENU_BUTTON_WIDTH, MENU_BUTTON_HEIGHT, text.to_string())
+}
+
+fn menu_text_input(index: usize, label: &str, placeholder: &str) -> TextInput {
+    let (x, y) = menu_slot(index);
+    TextInput::new(x, y, MENU_BUTTON_WIDTH, MENU_BUTTON_HEIGHT, label, placeholder)
+}
+
+// ตำแหน่งของ control ในหน้าตั้งค่า เรียงจากบนลงล่าง
+fn settings_slot(index: usize) -> (f32, f32) {
+    (
+        screen_width() / 2.0 - SLIDER_WIDTH / 2.0,
+        screen_height() / 2.0 - 140.0 + index as f32 * 60.0,
+    )
+}
+
+fn settings_slider(index: usize, label: &str, value: f32) -> Slider {
+    let (x, y) = settings_slot(index);
+    Slider::new(x, y, SLIDER_WIDTH, SLIDER_HEIGHT, label, value)
+}
+
+fn settings_screen_button(index: usize, text: &str) -> Button {
+    let (x, y) = settings_slot(index);
+    Button::new(x, y, SLIDER_WIDTH, MENU_BUTTON_HEIGHT, text.to_string())
+}
+
+fn board_label(board: &Board) -> String {
+    match board.preset() {
+        BoardPreset::Small => "Board: Small".to_string(),
+        BoardPreset::Medium => "Board: Medium".to_string(),
+        BoardPreset::Large => "Board: Large".to_string(),
+        BoardPreset::Custom => format!("Board: {}", board.to_text()),
+    }
+}
+
+fn boundary_label(rule: BoundaryRule) -> String {
+    match rule {
+        BoundaryRule::Wrap => "Edges: Wrap".to_string(),
+        BoundaryRule::Solid => "Edges: Solid".to_string(),
+        BoundaryRule::Bounce => "Edges: Bounce".to_string(),
+    }
+}
+
+fn mode_label(mode: GameMode) -> String {
+    match mode {
+        GameMode::Normal => "Mode: Normal".to_string(),
+        GameMode::Obstacle => "Mode: Obstacle".to_string(),
+        GameMode::Campaign => "Mode: Campaign".to_string(),
+        GameMode::Maze => "Mode: Maze".to_string(),
+    }
+}
+
+// ค่าที่ปุ่มในหน้าตั้งค่าวนให้เลือก
+const MAZE_REGEN_CHOICES: [Option<usize>; 3] = [None, Some(10), Some(20)];
+
+fn maze_regen_label(length: Option<usize>) -> String {
+    match length {
+        Some(length) => format!("Maze regen: every {}", length),
+        None => "Maze regen: Off".to_string(),
+    }
+}
+
+fn smooth_label(smooth: bool) -> String {
+    if smooth {
+        "Smooth movement: ON".to_string()
+    } else {
+        "Smooth movement: OFF".to_string()
+    }
+}
+
+fn mute_label(muted: bool) -> String {
+    if muted {
+        "Sound: OFF".to_string()
+    } else {
+        "Sound: ON".to_string()
+    }
+}
+
+// อ่าน --seed <n> จาก command line (ถ้ามี)
+fn seed_from_args() -> Option<u64> {
+    arg_value("--seed").and_then(|value| value.parse().ok())
+}
 
 struct SnakeGame {
-    snake: Snake,
-    food: Food,
-    game_over: bool,
-    frame_counter: u8,
+    sim: Simulation,
     state: GameState,
     start_button: Button,
-    exit_button: Button,
-    sound_button: Button,
     mode_button: Button,
-    high_score: usize,
+    seed_input: TextInput,
+    name_input: TextInput,
+    // ช่องกรอกชื่อบนหน้าชนะ ใช้เมื่อคะแนนติดตาราง
+    victory_name_input: TextInput,
+    settings_button: Button,
+    leaderboard_button: Button,
+    replay_button: Button,
+    exit_button: Button,
+    board_button: Button,
+    boundary_button: Button,
+    levels_button: Button,
+    watch_ai_button: Button,
+    watch_solver_button: Button,
+    master_slider: Slider,
+    music_slider: Slider,
+    effects_slider: Slider,
+    mute_button: Button,
+    maze_regen_button: Button,
+    smooth_button: Button,
+    back_button: Button,
+    leaderboard: Leaderboard,
+    leaderboard_mode: GameMode,
+    last_rank: Option<usize>,
     sound_manager: SoundManager,
-    power_up_manager: PowerUpManager,
+    music_manager: MusicManager,
+    settings: Settings,
+    levels: Vec<Arc<Level>>,
+    level_errors: Vec<LevelLoadError>,
+    level_index: usize,
+    // ด่านที่กำลังเล่น (None = สุ่มสิ่งกีดขวางตามโหมด)
+    selected_level: Option<Arc<Level>>,
+    campaign: CampaignProgress,
     game_mode: GameMode,
-    obstacles: Vec<game::Position>,
+    fixed_seed: Option<u64>,
+    replay_player: Option<ReplayPlayer>,
+    replay_paused: bool,
+    replay_step_requested: bool,
+    // ผู้บังคับงู: ผู้เล่นหรือ AI
+    controller: Box<dyn Controller>,
+    // เวลาจริงที่สะสมไว้แต่ยังไม่ได้เดินเป็น tick
+    tick_accumulator: f32,
 }
 
 impl SnakeGame {
-    fn new() -> Self {
-        let snake = Snake::new();
-        let food = Food::new();
-        let power_up_manager = PowerUpManager::new();
-
-        let start_button = Button::new(
-            screen_width() / 2.0 - 100.0,
-            screen_height() / 2.0 - 30.0,
-            200.0,
-            50.0,
-            "Start".to_string(),
-        );
+    fn new(mut sound_manager: SoundManager, mut music_manager: MusicManager) -> Self {
+        let fixed_seed = seed_from_args();
+        let sim = Simulation::new(GameConfig::default(), fixed_seed.unwrap_or(0));
 
-        let exit_button = Button::new(
-            screen_width() / 2.0 - 100.0,
-            screen_height() / 2.0 + 40.0,
-            200.0,
-            50.0,
-            "Exit".to_string(),
-        );
+        let start_button = menu_button(0, "Start");
+        let mode_button = menu_button(1, &mode_label(GameMode::Normal));
 
-        let sound_button = Button::new(
-            screen_width() / 2.0 - 100.0,
-            screen_height() / 2.0 + 110.0,
-            200.0,
-            50.0,
-            "Sound: ON".to_string(),
-        );
+        let mut seed_input = menu_text_input(2, "Seed", "Random");
+        seed_input.allowed = |c| c.is_ascii_digit();
+        if let Some(seed) = fixed_seed {
+            seed_input.set_value(&seed.to_string());
+        }
 
-        let mode_button = Button::new(
-            screen_width() / 2.0 - 100.0,
-            screen_height() / 2.0 + 180.0,
-            200.0,
-            50.0,
-            "Mode: Normal".to_string(),
-        );
+        let mut name_input = menu_text_input(3, "Name", "Player");
+        name_input.max_len = 12;
 
-        let high_score = load_high_score();
-        let sound_manager = SoundManager::new();
+        let mut victory_name_input = TextInput::new(0.0, 0.0, MENU_BUTTON_WIDTH, MENU_BUTTON_HEIGHT, "Name", "Player");
+        victory_name_input.max_len = name_input.max_len;
+
+        let settings_button = menu_button(4, "Settings");
+        let leaderboard_button = menu_button(5, "Leaderboard");
+        let replay_button = menu_button(6, "Watch Replay");
+        let exit_button = menu_button(7, "Exit");
+
+        let settings = Settings::load().unwrap_or_else(|e| {
+            eprintln!("Could not load settings: {}", e);
+            Settings::default()
+        });
+        sound_manager.mixer = settings.mixer();
+        music_manager.mixer = settings.mixer();
+
+        let board_button = menu_button(8, &board_label(&settings.board()));
+        let boundary_button = menu_button(9, &boundary_label(settings.boundary(GameMode::Normal)));
+        let levels_button = menu_button(10, "Levels");
+        let watch_ai_button = menu_button(11, "Watch AI");
+        let watch_solver_button = menu_button(12, "Watch Solver");
+
+        let master_slider = settings_slider(0, "Master", settings.master_volume);
+        let music_slider = settings_slider(1, "Music", settings.music_volume);
+        let effects_slider = settings_slider(2, "Effects", settings.effects_volume);
+        let mute_button = settings_screen_button(3, &mute_label(settings.muted));
+        let maze_regen_button = settings_screen_button(4, &maze_regen_label(settings.maze_regen_length));
+        let smooth_button = settings_screen_button(5, &smooth_label(settings.smooth_movement));
+        let back_button = settings_screen_button(6, "Back");
+
+        let leaderboard = load_leaderboard().unwrap_or_else(|e| {
+            eprintln!("Could not load leaderboard: {}", e);
+            Leaderboard::default()
+        });
+
+        let campaign = CampaignProgress::load().unwrap_or_else(|e| {
+            eprintln!("Could not load campaign progress: {}", e);
+            CampaignProgress::default()
+        });
 
         SnakeGame {
-            snake,
-            food,
-            game_over: false,
-            frame_counter: 0,
+            sim,
             state: GameState::Menu,
             start_button,
-            exit_button,
-            sound_button,
             mode_button,
-            high_score,
+            seed_input,
+            name_input,
+            victory_name_input,
+            settings_button,
+            leaderboard_button,
+            replay_button,
+            exit_button,
+            board_button,
+            boundary_button,
+            levels_button,
+            watch_ai_button,
+            watch_solver_button,
+            master_slider,
+            music_slider,
+            effects_slider,
+            mute_button,
+            maze_regen_button,
+            smooth_button,
+            back_button,
+            leaderboard,
+            leaderboard_mode: GameMode::Normal,
+            last_rank: None,
             sound_manager,
-            power_up_manager,
+            music_manager,
+            settings,
+            levels: Vec::new(),
+            level_errors: Vec::new(),
+            level_index: 0,
+            selected_level: None,
+            campaign,
             game_mode: GameMode::Normal,
-            obstacles: Vec::new(),
+            fixed_seed,
+            replay_player: None,
+            replay_paused: false,
+            replay_step_requested: false,
+            controller: human_controller(),
+            tick_accumulator: 0.0,
         }
     }
 
-    fn generate_obstacles(&self) -> Vec<game::Position> {
-        let mut obstacles = Vec::new();
-        let mut rng = thread_rng();
-        
-        let num_obstacles = rng.gen_range(5..9);
-        
-        for _ in 0..num_obstacles {
-            loop {
-                let pos = game::Position {
-                    x: rng.gen_range(0..GRID_WIDTH),
-                    y: rng.gen_range(0..GRID_HEIGHT),
-                };
-                if !self.snake.contains(&pos) && pos != self.food.position && !obstacles.contains(&pos) {
-                    obstacles.push(pos);
-                    break;
-                }
-            }
+    fn player_name(&self) -> String {
+        if self.name_input.value.is_empty() {
+            "Player".to_string()
+        } else {
+            self.name_input.value.clone()
         }
-        obstacles
     }
 
     fn save_current_score(&mut self) {
-        let score = self.snake.len() - 1;
-        if score > self.high_score {
-            self.high_score = score;
-            save_high_score(self.high_score);
+        // เกมที่ AI เล่นไม่นับเข้าตารางคะแนน
+        if !self.controller.is_human() {
+            return;
+        }
+        let entry = LeaderboardEntry {
+            name: self.player_name(),
+            score: self.sim.score(),
+            length: self.sim.snake.len(),
+            ticks: self.sim.tick,
+            timestamp: unix_timestamp(),
+            seed: self.sim.seed,
+            power_ups_collected: self.sim.power_ups_collected,
+        };
+        self.last_rank = self.leaderboard.insert(self.sim.config.game_mode, entry);
+        if self.last_rank.is_some() {
+            if let Err(e) = save_leaderboard(&self.leaderboard) {
+                eprintln!("Could not save leaderboard: {}", e);
+            }
         }
     }
 
-    fn reset_game(&mut self) {
-        self.snake.reset();
-        self.food.respawn(&self.snake.body);
-        self.game_over = false;
-        self.frame_counter = 0;
-        self.power_up_manager.reset();
-        
-        if self.game_mode == GameMode::Obstacle {
-            self.obstacles = self.generate_obstacles();
+    // เข้าหน้าชนะ ถ้าคะแนนติดตารางให้กรอกชื่อก่อนบันทึก
+    fn enter_victory(&mut self) {
+        self.last_rank = None;
+        if self.controller.is_human() && self.leaderboard.qualifies(self.sim.config.game_mode, self.sim.score()) {
+            self.victory_name_input.set_value(&self.name_input.value);
+            self.victory_name_input.start_editing();
         } else {
-            self.obstacles.clear();
+            self.victory_name_input.editing = false;
+        }
+        self.state = GameState::Victory;
+    }
+
+    fn save_victory_entry(&mut self) {
+        let name = self.victory_name_input.value.clone();
+        self.name_input.set_value(&name);
+        self.save_current_score();
+    }
+
+    fn reset_game(&mut self) {
+        self.sim.config = match &self.selected_level {
+            Some(level) => GameConfig::for_level(Arc::clone(level)),
+            None => GameConfig::new(
+                self.game_mode,
+                self.settings.board(),
+                self.settings.boundary(self.game_mode),
+            ),
+        };
+        if self.game_mode == GameMode::Maze {
+            self.sim.config.maze_regen_length = self.settings.maze_regen_length;
         }
+        self.sim.seed = self.fixed_seed.unwrap_or_else(::rand::random);
+        self.sim.reset();
+        self.controller.reset();
+        self.last_rank = None;
     }
 
     fn update_button_positions(&mut self) {
-        let screen_w = screen_width();
-        let screen_h = screen_height();
-        
-        self.start_button.update_position(
-            screen_w / 2.0 - 100.0,
-            screen_h / 2.0 - 30.0,
-        );
-        
-        self.exit_button.update_position(
-            screen_w / 2.0 - 100.0,
-            screen_h / 2.0 + 40.0,
-        );
-        
-        self.sound_button.update_position(
-            screen_w / 2.0 - 100.0,
-            screen_h / 2.0 + 110.0,
-        );
-        
-        self.mode_button.update_position(
-            screen_w / 2.0 - 100.0,
-            screen_h / 2.0 + 180.0,
+        let (x, y) = menu_slot(0);
+        self.start_button.update_position(x, y);
+
+        let (x, y) = menu_slot(1);
+        self.mode_button.update_position(x, y);
+
+        let (x, y) = menu_slot(2);
+        self.seed_input.update_position(x, y);
+
+        let (x, y) = menu_slot(3);
+        self.name_input.update_position(x, y);
+
+        let (x, y) = menu_slot(4);
+        self.settings_button.update_position(x, y);
+
+        let (x, y) = menu_slot(5);
+        self.leaderboard_button.update_position(x, y);
+
+        let (x, y) = menu_slot(6);
+        self.replay_button.update_position(x, y);
+
+        let (x, y) = menu_slot(7);
+        self.exit_button.update_position(x, y);
+
+        let (x, y) = menu_slot(8);
+        self.board_button.update_position(x, y);
+
+        let (x, y) = menu_slot(9);
+        self.boundary_button.update_position(x, y);
+
+        let (x, y) = menu_slot(10);
+        self.levels_button.update_position(x, y);
+
+        let (x, y) = menu_slot(11);
+        self.watch_ai_button.update_position(x, y);
+
+        let (x, y) = menu_slot(12);
+        self.watch_solver_button.update_position(x, y);
+
+        self.victory_name_input.update_position(
+            screen_width() / 2.0 - MENU_BUTTON_WIDTH / 2.0,
+            screen_height() / 2.0 + 60.0,
         );
+
+        let (x, y) = settings_slot(0);
+        self.master_slider.update_position(x, y);
+
+        let (x, y) = settings_slot(1);
+        self.music_slider.update_position(x, y);
+
+        let (x, y) = settings_slot(2);
+        self.effects_slider.update_position(x, y);
+
+        let (x, y) = settings_slot(3);
+        self.mute_button.update_position(x, y);
+
+        let (x, y) = settings_slot(4);
+        self.maze_regen_button.update_position(x, y);
+
+        let (x, y) = settings_slot(5);
+        self.smooth_button.update_position(x, y);
+
+        let (x, y) = settings_slot(6);
+        self.back_button.update_position(x, y);
     }
 
-    fn update(&mut self) {
-        self.frame_counter += 1;
+    fn apply_settings(&mut self) {
+        self.sound_manager.mixer = self.settings.mixer();
+        self.music_manager.mixer = self.settings.mixer();
+    }
 
-        let speed_threshold = (10.0 / self.power_up_manager.speed_multiplier) as u8;
-        if self.frame_counter < speed_threshold {
-            return;
+    fn save_settings(&self) {
+        if let Err(e) = self.settings.save() {
+            eprintln!("Could not save settings: {}", e);
         }
-        self.frame_counter = 0;
+    }
 
-        self.power_up_manager.update(&self.snake.body, &self.food.position);
+    fn update_music(&mut self) {
+        self.music_manager.set_track(MusicTrack::for_state(&self.state));
+        self.music_manager.set_intensity(self.sim.power_up_manager.speed_multiplier, self.sim.snake.len());
+        self.music_manager.update(get_frame_time());
+    }
 
-        let new_head = self.snake.move_snake();
+    // อ่านโฟลเดอร์ด่านใหม่ทุกครั้งที่เปิดหน้าเลือกด่าน ไฟล์ที่เพิ่งวางไว้จึงขึ้นทันที
+    fn reload_levels(&mut self) {
+        let (levels, errors) = load_all_levels();
+        self.levels = levels.into_iter().map(Arc::new).collect();
+        self.level_errors = errors;
+        self.level_index = self.level_index.min(self.levels.len().saturating_sub(1));
+    }
+
+    fn start_level(&mut self, level: Arc<Level>) {
+        self.game_mode = if level.is_campaign() { GameMode::Campaign } else { GameMode::Obstacle };
+        self.mode_button.text = mode_label(self.game_mode);
+        self.boundary_button.text = boundary_label(self.settings.boundary(self.game_mode));
+        self.selected_level = Some(level);
+        self.reset_game();
+        self.state = GameState::Playing;
+    }
 
-        if (self.snake.contains(&new_head) || self.obstacles.contains(&new_head)) && !self.power_up_manager.ghost_mode {
-            self.sound_manager.play_crash_sound();
-            self.save_current_score();
-            self.game_over = true;
-            self.state = GameState::GameOver;
+    // เริ่ม Campaign ที่ด่านแรกที่ยังไม่ผ่าน
+    fn start_campaign(&mut self) {
+        self.reload_levels();
+        let campaign = campaign_levels(&self.levels);
+        if campaign.is_empty() {
+            eprintln!("No campaign levels found");
             return;
         }
+        let index = self.campaign.next_index(&campaign);
+        self.start_level(Arc::clone(&campaign[index]));
+    }
 
-        self.snake.grow(new_head);
+    fn next_campaign_level(&self) -> Option<Arc<Level>> {
+        let current = self.sim.config.level.as_ref()?;
+        let campaign = campaign_levels(&self.levels);
+        let index = campaign.iter().position(|level| level.id == current.id)?;
+        campaign.get(index + 1).cloned()
+    }
 
-        // ตรวจสอบการชนกับ Power-up
-        if let Some(power_type) = self.power_up_manager.check_collision(&new_head) {
-            match power_type {
-                PowerUpType::SpeedBoost => {
-                    self.sound_manager.play_power_up_sound("speed boost");
+    fn complete_level(&mut self) {
+        if !self.controller.is_human() {
+            return;
+        }
+        if let Some(level) = self.sim.config.level.clone() {
+            if self.campaign.complete(&level.id) {
+                if let Err(e) = self.campaign.save() {
+                    eprintln!("Could not save campaign progress: {}", e);
                 }
-                PowerUpType::Shrink => {
-                    for _ in 0..2 {
-                        self.snake.shrink();
+            }
+        }
+    }
+
+    fn start_replay(&mut self, mut replay: Replay) {
+        if replay.level_id.is_some() && self.levels.is_empty() {
+            self.reload_levels();
+        }
+        if let Err(e) = replay.attach_level(&self.levels) {
+            eprintln!("Could not start replay: {}", e);
+            return;
+        }
+        self.sim = Simulation::new(replay.config.clone(), replay.seed);
+        self.replay_player = Some(ReplayPlayer::new(replay));
+        self.replay_paused = false;
+        self.replay_step_requested = false;
+        self.state = GameState::Replay;
+    }
+
+    fn save_replay(&self) {
+        // ไม่เขียนทับ replay ล่าสุดของผู้เล่น
+        if !self.controller.is_human() {
+            return;
+        }
+        if let Err(e) = self.sim.recording.save(&data_path(LAST_REPLAY_FILE)) {
+            eprintln!("Could not save replay: {}", e);
+        }
+    }
+
+    fn load_replay(path: &Path) -> Option<Replay> {
+        match Replay::load(path) {
+            Ok(replay) => Some(replay),
+            Err(e) => {
+                eprintln!("Could not load replay {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    // แปลงเวลาที่ผ่านไปของเฟรมนี้เป็นจำนวน tick ที่ต้องเดิน เศษที่เหลือเก็บไว้รอบหน้า
+    fn take_ticks(&mut self) -> u32 {
+        let tick_time = 1.0 / TICKS_PER_SECOND as f32;
+        self.tick_accumulator += get_frame_time().min(MAX_FRAME_TIME);
+        let ticks = (self.tick_accumulator / tick_time) as u32;
+        self.tick_accumulator -= ticks as f32 * tick_time;
+        ticks
+    }
+
+    fn update(&mut self) {
+        self.controller.poll();
+        for _ in 0..self.take_ticks() {
+            let events = controller::step(self.controller.as_mut(), &mut self.sim);
+            self.handle_events(events);
+            if self.state != GameState::Playing {
+                break;
+            }
+        }
+    }
+
+    fn step_replay(&mut self) -> Vec<GameEvent> {
+        match self.replay_player.as_mut() {
+            Some(player) => controller::step(player, &mut self.sim),
+            None => self.sim.step(None),
+        }
+    }
+
+    fn update_replay(&mut self) {
+        if self.sim.is_over() {
+            return;
+        }
+
+        if self.replay_paused {
+            self.tick_accumulator = 0.0;
+            if self.replay_step_requested {
+                self.replay_step_requested = false;
+                // เดินหน้าจนงูขยับหนึ่งช่อง
+                loop {
+                    let events = self.step_replay();
+                    let moved = events.iter().any(|e| matches!(e, GameEvent::Moved));
+                    self.handle_events(events);
+                    if moved || self.sim.is_over() {
+                        break;
                     }
-                    self.sound_manager.play_power_up_sound("shrink");
-                }
-                PowerUpType::GhostMode => {
-                    self.sound_manager.play_power_up_sound("ghost mode");
                 }
             }
+            return;
         }
 
-        if new_head == self.food.position {
-            self.food.respawn(&self.snake.body);
-            self.sound_manager.play_eat_sound();
-        } else {
-            self.snake.shrink();
+        let ticks = self.take_ticks();
+        let steps = if is_key_down(KeyCode::F) { ticks * REPLAY_FAST_FORWARD } else { ticks };
+        for _ in 0..steps {
+            let events = self.step_replay();
+            self.handle_events(events);
+        }
+    }
+
+    fn handle_events(&mut self, events: Vec<GameEvent>) {
+        for event in events {
+            match event {
+                GameEvent::AteFood => self.sound_manager.play_eat(self.sim.snake.len()),
+                GameEvent::PowerUpCollected(power_type) => {
+                    self.sound_manager.play(SoundEffect::PowerUp(power_type));
+                }
+                GameEvent::PowerUpExpired(power_type) => {
+                    self.sound_manager.play(SoundEffect::PowerUpExpired(power_type));
+                }
+                GameEvent::Died(_) => {
+                    self.sound_manager.play(SoundEffect::Crash);
+                    if self.state == GameState::Playing {
+                        self.save_current_score();
+                        self.save_replay();
+                        self.state = GameState::GameOver;
+                    }
+                }
+                GameEvent::BoardFilled => {
+                    self.sound_manager.play(SoundEffect::Victory);
+                    if self.state == GameState::Playing {
+                        // เต็มกระดานในด่าน Campaign ถือว่าผ่านด่าน
+                        if self.sim.config.game_mode == GameMode::Campaign {
+                            self.complete_level();
+                        }
+                        self.save_replay();
+                        self.enter_victory();
+                    }
+                }
+                GameEvent::LevelComplete => {
+                    self.sound_manager.play(SoundEffect::LevelComplete);
+                    if self.state == GameState::Playing {
+                        self.complete_level();
+                        self.save_current_score();
+                        self.save_replay();
+                        self.state = GameState::GameOver;
+                    }
+                }
+                _ => {}
+            }
         }
     }
 
@@ -216,20 +627,29 @@ impl SnakeGame {
         draw_text(
             "SNAKE GAME",
             screen_w / 2.0 - 120.0,
-            screen_h / 2.0 - 100.0,
+            screen_h / 2.0 - 210.0,
             40.0,
             GREEN,
         );
         
         self.start_button.draw();
-        self.exit_button.draw();
-        self.sound_button.draw();
         self.mode_button.draw();
+        self.seed_input.draw();
+        self.name_input.draw();
+        self.settings_button.draw();
+        self.leaderboard_button.draw();
+        self.replay_button.draw();
+        self.exit_button.draw();
+        self.board_button.draw();
+        self.boundary_button.draw();
+        self.levels_button.draw();
+        self.watch_ai_button.draw();
+        self.watch_solver_button.draw();
         
         draw_text(
-            &format!("High Score: {}", self.high_score),
+            &format!("High Score: {}", self.leaderboard.best_score(self.game_mode)),
             screen_w / 2.0 - 90.0,
-            screen_h / 2.0 - 60.0,
+            screen_h / 2.0 - 160.0,
             30.0,
             YELLOW,
         );
@@ -248,38 +668,59 @@ impl SnakeGame {
 
         let screen_w = screen_width();
         let screen_h = screen_height();
-        let cell_size = get_cell_size();
+        let board = &self.sim.config.board;
+        let cell_size = get_cell_size(board);
 
-        let game_width = GRID_WIDTH as f32 * cell_size;
-        let game_height = GRID_HEIGHT as f32 * cell_size;
+        let game_width = board.width as f32 * cell_size;
+        let game_height = board.height as f32 * cell_size;
         let offset_x = (screen_w - game_width) / 2.0;
         let offset_y = (screen_h - game_height) / 2.0;
 
-        draw_rectangle_lines(offset_x, offset_y, game_width, game_height, 2.0, WHITE);
+        // ขอบกระดานบอกกติกา: ขาวบาง = ทะลุได้, แดงหนา = กำแพง, ฟ้าสองชั้น = เด้งกลับ
+        match self.sim.config.boundary {
+            BoundaryRule::Wrap => {
+                draw_rectangle_lines(offset_x, offset_y, game_width, game_height, 2.0, WHITE);
+            }
+            BoundaryRule::Solid => {
+                draw_rectangle_lines(offset_x - 3.0, offset_y - 3.0, game_width + 6.0, game_height + 6.0, 6.0, RED);
+            }
+            BoundaryRule::Bounce => {
+                draw_rectangle_lines(offset_x, offset_y, game_width, game_height, 2.0, SKYBLUE);
+                draw_rectangle_lines(offset_x - 5.0, offset_y - 5.0, game_width + 10.0, game_height + 10.0, 2.0, SKYBLUE);
+            }
+        }
 
         // วาดอาหาร
         draw_rectangle(
-            offset_x + self.food.position.x as f32 * cell_size,
-            offset_y + self.food.position.y as f32 * cell_size,
+            offset_x + self.sim.food.position.x as f32 * cell_size,
+            offset_y + self.sim.food.position.y as f32 * cell_size,
             cell_size,
             cell_size,
             RED,
         );
 
-        // วาดงู
-        for (i, seg) in self.snake.body.iter().enumerate() {
+        // วาดงู วาดหางก่อนเพื่อให้หัวทับปล้องที่เลื่อนซ้อนกัน
+        let progress = if self.settings.smooth_movement {
+            self.sim.move_progress(self.tick_accumulator * TICKS_PER_SECOND as f32)
+        } else {
+            1.0
+        };
+        let board_rect = Rect::new(offset_x, offset_y, game_width, game_height);
+        for (i, (x, y)) in self.sim.interpolated_body(progress).into_iter().enumerate().rev() {
             let color = if i == 0 { GREEN } else { DARKGREEN };
-            draw_rectangle(
-                offset_x + seg.x as f32 * cell_size,
-                offset_y + seg.y as f32 * cell_size,
-                cell_size,
-                cell_size,
-                color,
-            );
+            // ปล้องที่กำลังทะลุขอบ วาดสองฝั่งแล้วตัดส่วนที่เกินกระดานทิ้ง
+            let wrapped_x = x.rem_euclid(board.width as f32);
+            let wrapped_y = y.rem_euclid(board.height as f32);
+            for (cell_x, cell_y) in [(x, y), (wrapped_x, wrapped_y)] {
+                let cell = Rect::new(offset_x + cell_x * cell_size, offset_y + cell_y * cell_size, cell_size, cell_size);
+                if let Some(visible) = cell.intersect(board_rect) {
+                    draw_rectangle(visible.x, visible.y, visible.w, visible.h, color);
+                }
+            }
         }
 
         // วาด Power-ups
-        for power_up in &self.power_up_manager.power_ups {
+        for power_up in &self.sim.power_up_manager.power_ups {
             let color = match power_up.power_type {
                 PowerUpType::SpeedBoost => YELLOW,
                 PowerUpType::Shrink => ORANGE,
@@ -307,7 +748,7 @@ impl SnakeGame {
         }
 
         // วาด Obstacles
-        for obstacle in &self.obstacles {
+        for obstacle in &self.sim.obstacles {
             draw_rectangle(
                 offset_x + obstacle.x as f32 * cell_size,
                 offset_y + obstacle.y as f32 * cell_size,
@@ -317,19 +758,51 @@ impl SnakeGame {
             );
         }
 
+        // วาด hazard: ตัน = ทึบ, เตือน = กรอบกระพริบ, ประตูเปิด = กรอบจาง
+        for hazard in &self.sim.hazards {
+            let solid_color = match hazard.spec.kind {
+                HazardKind::Patrol { .. } => MAROON,
+                HazardKind::Gate { .. } => GRAY,
+                HazardKind::Periodic { .. } => RED,
+            };
+            for cell in hazard.cells() {
+                let x = offset_x + cell.x as f32 * cell_size;
+                let y = offset_y + cell.y as f32 * cell_size;
+                match hazard.phase {
+                    HazardPhase::Solid => draw_rectangle(x, y, cell_size, cell_size, solid_color),
+                    HazardPhase::Warning => {
+                        if (self.sim.tick / 8).is_multiple_of(2) {
+                            draw_rectangle_lines(x, y, cell_size, cell_size, 2.0, ORANGE);
+                        }
+                    }
+                    HazardPhase::Open => draw_rectangle_lines(x, y, cell_size, cell_size, 1.0, DARKGRAY),
+                }
+            }
+        }
+
         draw_text(
-            &format!("Score: {}", self.snake.len() - 1),
+            &format!("Score: {}", self.sim.score()),
             10.0,
             screen_h - 10.0,
             20.0,
             WHITE,
         );
 
+        if let (Some(goal), Some(progress)) = (self.sim.goal(), self.sim.goal_progress()) {
+            draw_text(
+                &format!("Goal: {} ({}/{})", goal.describe(), progress.min(goal.target()), goal.target()),
+                screen_w / 2.0 - 120.0,
+                20.0,
+                20.0,
+                SKYBLUE,
+            );
+        }
+
         let mut y_offset = 30.0;
-        for (power_type, duration) in &self.power_up_manager.active_power_ups {
+        for (power_type, duration) in &self.sim.power_up_manager.active_power_ups {
             let text = match power_type {
-                PowerUpType::SpeedBoost => format!("speed boost: {}s", duration / 60),
-                PowerUpType::GhostMode => format!("ghost mode: {}s", duration / 60),
+                PowerUpType::SpeedBoost => format!("speed boost: {}s", duration.div_ceil(TICKS_PER_SECOND as u32)),
+                PowerUpType::GhostMode => format!("ghost mode: {}s", duration.div_ceil(TICKS_PER_SECOND as u32)),
                 PowerUpType::Shrink => "shrink active".to_string(),
             };
             draw_text(
@@ -349,6 +822,11 @@ impl SnakeGame {
             20.0,
             YELLOW,
         );
+
+        if !self.controller.is_human() && self.state != GameState::Replay {
+            let label = format!("{} PLAYING  ESC menu", self.controller.name());
+            draw_text(&label, 10.0, 20.0, 20.0, SKYBLUE);
+        }
     }
 
     fn draw_game_over(&self) {
@@ -357,24 +835,55 @@ impl SnakeGame {
         let screen_w = screen_width();
         let screen_h = screen_height();
         
+        let level_complete = self.sim.state == SimulationState::LevelComplete;
+        let (title, title_color) = match self.sim.config.game_mode {
+            GameMode::Campaign if level_complete => ("LEVEL COMPLETE", GREEN),
+            GameMode::Campaign => ("LEVEL FAILED", RED),
+            _ => ("GAME OVER", WHITE),
+        };
         draw_text(
-            "GAME OVER",
-            screen_w / 2.0 - 100.0,
+            title,
+            screen_w / 2.0 - 130.0,
             screen_h / 2.0 - 50.0,
             40.0,
-            WHITE,
+            title_color,
         );
+
+        if let Some(cause) = self.sim.death_cause {
+            let text = match cause {
+                DeathCause::SelfCollision => "You ran into yourself",
+                DeathCause::Obstacle => "You hit an obstacle",
+                DeathCause::Wall => "You hit the wall",
+            };
+            draw_text(text, screen_w / 2.0 - 120.0, screen_h / 2.0 - 130.0, 25.0, ORANGE);
+        }
         
         draw_text(
-            &format!("High Score: {}", self.high_score),
+            &format!("High Score: {}", self.leaderboard.best_score(self.sim.config.game_mode)),
             screen_w / 2.0 - 90.0,
             screen_h / 2.0 - 10.0,
             30.0,
             YELLOW,
         );
+
+        if let Some(rank) = self.last_rank {
+            draw_text(
+                &format!("New leaderboard entry: #{}", rank + 1),
+                screen_w / 2.0 - 130.0,
+                screen_h / 2.0 - 90.0,
+                25.0,
+                GREEN,
+            );
+        }
         
+        let restart_hint = match self.sim.config.game_mode {
+            GameMode::Campaign if level_complete && self.next_campaign_level().is_some() => "Press ENTER for Next Level",
+            GameMode::Campaign if level_complete => "Campaign complete! ENTER to replay level",
+            GameMode::Campaign => "Press ENTER to Retry",
+            _ => "Press ENTER to Restart",
+        };
         draw_text(
-            "Press ENTER to Restart",
+            restart_hint,
             screen_w / 2.0 - 130.0,
             screen_h / 2.0,
             25.0,
@@ -382,12 +891,283 @@ impl SnakeGame {
         );
         
         draw_text(
-            "Press ESC for Menu",
-            screen_w / 2.0 - 100.0,
+            "Press R to Watch Replay, ESC for Menu",
+            screen_w / 2.0 - 190.0,
             screen_h / 2.0 + 30.0,
             25.0,
             GRAY,
         );
+
+        draw_text(
+            &format!("Seed: {}", self.sim.seed),
+            screen_w / 2.0 - 100.0,
+            screen_h / 2.0 + 60.0,
+            25.0,
+            GRAY,
+        );
+    }
+
+    fn draw_victory(&self) {
+        self.draw_game();
+
+        let screen_w = screen_width();
+        let screen_h = screen_height();
+
+        draw_rectangle(0.0, 0.0, screen_w, screen_h, Color::new(0.0, 0.0, 0.0, 0.75));
+
+        draw_text("YOU WIN!", screen_w / 2.0 - 100.0, screen_h / 2.0 - 170.0, 50.0, GOLD);
+        draw_text(
+            "The snake filled the whole board",
+            screen_w / 2.0 - 170.0,
+            screen_h / 2.0 - 135.0,
+            25.0,
+            GREEN,
+        );
+
+        let seconds = self.sim.tick / TICKS_PER_SECOND;
+        let stats = [
+            format!("Score: {}", self.sim.score()),
+            format!("Length: {}", self.sim.snake.len()),
+            format!("Food eaten: {}", self.sim.food_eaten),
+            format!("Power-ups: {}", self.sim.power_ups_collected),
+            format!("Time: {}:{:02}", seconds / 60, seconds % 60),
+            format!("Board: {}x{}", self.sim.config.board.width, self.sim.config.board.height),
+        ];
+        for (i, line) in stats.iter().enumerate() {
+            let column = (i % 2) as f32;
+            let row = (i / 2) as f32;
+            draw_text(
+                line,
+                screen_w / 2.0 - 170.0 + column * 190.0,
+                screen_h / 2.0 - 90.0 + row * 30.0,
+                25.0,
+                WHITE,
+            );
+        }
+
+        if self.victory_name_input.editing {
+            draw_text(
+                "New high score! Type your name and press ENTER",
+                screen_w / 2.0 - 230.0,
+                screen_h / 2.0 + 45.0,
+                22.0,
+                YELLOW,
+            );
+            self.victory_name_input.draw();
+            return;
+        }
+
+        let rank_text = match self.last_rank {
+            Some(rank) => format!("New leaderboard entry: #{}", rank + 1),
+            None => format!("High Score: {}", self.leaderboard.best_score(self.sim.config.game_mode)),
+        };
+        draw_text(&rank_text, screen_w / 2.0 - 130.0, screen_h / 2.0 + 60.0, 25.0, YELLOW);
+
+        let restart_hint = if self.next_campaign_level().is_some() {
+            "Press ENTER for Next Level"
+        } else {
+            "Press ENTER to Play Again"
+        };
+        draw_text(restart_hint, screen_w / 2.0 - 130.0, screen_h / 2.0 + 100.0, 25.0, GRAY);
+        draw_text(
+            "Press R to Watch Replay, ESC for Menu",
+            screen_w / 2.0 - 190.0,
+            screen_h / 2.0 + 130.0,
+            25.0,
+            GRAY,
+        );
+        draw_text(
+            &format!("Seed: {}", self.sim.seed),
+            screen_w / 2.0 - 100.0,
+            screen_h / 2.0 + 160.0,
+            25.0,
+            GRAY,
+        );
+    }
+
+    fn draw_replay(&self) {
+        self.draw_game();
+
+        let screen_w = screen_width();
+
+        let status = if self.sim.is_over() {
+            "REPLAY FINISHED".to_string()
+        } else if self.replay_paused {
+            format!("REPLAY PAUSED  tick {}", self.sim.tick)
+        } else {
+            format!("REPLAY  tick {}", self.sim.tick)
+        };
+        draw_text(&status, 10.0, 20.0, 20.0, YELLOW);
+
+        draw_text(
+            "SPACE pause  F fast-forward  . step  ESC menu",
+            10.0,
+            40.0,
+            16.0,
+            GRAY,
+        );
+
+        draw_text(
+            &format!("Seed: {}", self.sim.seed),
+            screen_w - 200.0,
+            40.0,
+            16.0,
+            GRAY,
+        );
+    }
+
+    fn draw_settings(&self) {
+        clear_background(BLACK);
+
+        let screen_w = screen_width();
+        let screen_h = screen_height();
+
+        draw_rectangle_lines(0.0, 0.0, screen_w, screen_h, 2.0, WHITE);
+
+        draw_text(
+            "SETTINGS",
+            screen_w / 2.0 - 80.0,
+            screen_h / 2.0 - 200.0,
+            40.0,
+            GREEN,
+        );
+
+        // build ที่ไม่มี feature "audio" ปรับเสียงได้แต่จะไม่มีเสียงออก
+        if !cfg!(feature = "audio") {
+            let hint = "Sound is off in this build - rebuild with --features audio";
+            let width = measure_text(hint, None, 18, 1.0).width;
+            draw_text(hint, screen_w / 2.0 - width / 2.0, screen_h / 2.0 - 172.0, 18.0, GRAY);
+        }
+
+        self.master_slider.draw();
+        self.music_slider.draw();
+        self.effects_slider.draw();
+        self.mute_button.draw();
+        self.maze_regen_button.draw();
+        self.smooth_button.draw();
+        self.back_button.draw();
+    }
+
+    fn draw_leaderboard(&self) {
+        clear_background(BLACK);
+
+        let screen_w = screen_width();
+        let screen_h = screen_height();
+
+        draw_rectangle_lines(0.0, 0.0, screen_w, screen_h, 2.0, WHITE);
+
+        draw_text(
+            &format!("LEADERBOARD - {}", self.leaderboard_mode.name().to_uppercase()),
+            40.0,
+            50.0,
+            35.0,
+            GREEN,
+        );
+
+        let columns = [40.0, 80.0, 220.0, 290.0, 360.0, 430.0, 540.0, 680.0];
+        let headers = ["#", "Name", "Score", "Length", "Time", "Date", "Seed", "Power-ups"];
+        for (x, header) in columns.iter().zip(headers) {
+            draw_text(header, *x, 100.0, 20.0, YELLOW);
+        }
+
+        let entries = self.leaderboard.entries(self.leaderboard_mode);
+        if entries.is_empty() {
+            draw_text("No runs recorded yet", 40.0, 140.0, 20.0, GRAY);
+        }
+
+        for (i, entry) in entries.iter().enumerate() {
+            let y = 130.0 + i as f32 * 28.0;
+            let cells = [
+                format!("{}", i + 1),
+                entry.name.clone(),
+                entry.score.to_string(),
+                entry.length.to_string(),
+                format!("{}s", entry.ticks / TICKS_PER_SECOND),
+                format_date(entry.timestamp),
+                entry.seed.to_string(),
+                entry.power_ups_collected.to_string(),
+            ];
+            for (x, cell) in columns.iter().zip(cells.iter()) {
+                draw_text(cell, *x, y, 20.0, WHITE);
+            }
+        }
+
+        draw_text(
+            "LEFT/RIGHT change mode  ESC menu",
+            40.0,
+            screen_h - 30.0,
+            20.0,
+            GRAY,
+        );
+    }
+
+    fn draw_level_select(&self) {
+        clear_background(BLACK);
+
+        let screen_w = screen_width();
+        let screen_h = screen_height();
+
+        draw_rectangle_lines(0.0, 0.0, screen_w, screen_h, 2.0, WHITE);
+
+        draw_text("LEVELS", 40.0, 50.0, 35.0, GREEN);
+
+        if self.levels.is_empty() {
+            draw_text("No levels found", 40.0, 100.0, 20.0, GRAY);
+        }
+
+        for (i, level) in self.levels.iter().enumerate() {
+            let y = 100.0 + i as f32 * 28.0;
+            let unlocked = self.is_level_unlocked(level);
+            let color = match (i == self.level_index, unlocked) {
+                (true, _) => YELLOW,
+                (false, true) => WHITE,
+                (false, false) => DARKGRAY,
+            };
+            if i == self.level_index {
+                draw_text(">", 40.0, y, 20.0, color);
+            }
+            draw_text(&level.name, 60.0, y, 20.0, color);
+            draw_text(&level.board.to_text(), 300.0, y, 20.0, color);
+            draw_text(level.boundary.name(), 400.0, y, 20.0, color);
+            let detail = match level.goal {
+                Some(_) if !unlocked => "locked".to_string(),
+                Some(goal) if self.campaign.is_completed(&level.id) => format!("{} (done)", goal.describe()),
+                Some(goal) => goal.describe(),
+                None => level.id.clone(),
+            };
+            draw_text(&detail, 500.0, y, 20.0, GRAY);
+        }
+
+        // ไฟล์ด่านที่ผิดรูปแบบ แสดงพร้อมบรรทัด/คอลัมน์ที่ผิด
+        let errors_y = 130.0 + self.levels.len() as f32 * 28.0;
+        for (i, failed) in self.level_errors.iter().enumerate() {
+            draw_text(
+                &format!("{}: {}", failed.file_name, failed.error),
+                40.0,
+                errors_y + i as f32 * 22.0,
+                16.0,
+                RED,
+            );
+        }
+
+        draw_text(
+            "UP/DOWN choose  ENTER play  ESC menu",
+            40.0,
+            screen_h - 30.0,
+            20.0,
+            GRAY,
+        );
+    }
+
+    fn is_level_unlocked(&self, level: &Level) -> bool {
+        if !level.is_campaign() {
+            return true;
+        }
+        let campaign = campaign_levels(&self.levels);
+        campaign
+            .iter()
+            .position(|l| l.id == level.id)
+            .is_some_and(|index| self.campaign.is_unlocked(&campaign, index))
     }
 
     fn draw(&self) {
@@ -414,54 +1194,188 @@ impl SnakeGame {
                 );
             },
             GameState::GameOver => self.draw_game_over(),
+            GameState::Replay => self.draw_replay(),
+            GameState::Leaderboard => self.draw_leaderboard(),
+            GameState::Settings => self.draw_settings(),
+            GameState::LevelSelect => self.draw_level_select(),
+            GameState::Victory => self.draw_victory(),
         }
     }
 
     fn handle_input(&mut self) {
         match self.state {
             GameState::Menu => {
-                if self.start_button.is_clicked() {
-                    self.reset_game();
-                    self.state = GameState::Playing;
-                } else if self.exit_button.is_clicked() {
-                    std::process::exit(0);
-                } else if self.sound_button.is_clicked() {
-                    self.sound_manager.sound_enabled = !self.sound_manager.sound_enabled;
-                    self.sound_button.text = if self.sound_manager.sound_enabled {
-                        "Sound: ON".to_string()
-                    } else {
-                        "Sound: OFF".to_string()
-                    };
-                } else if self.mode_button.is_clicked() {
-                    self.game_mode = if self.game_mode == GameMode::Normal {
-                        GameMode::Obstacle
+                if self.seed_input.editing {
+                    if self.seed_input.handle_input() {
+                        self.fixed_seed = self.seed_input.value.parse().ok();
+                        if self.fixed_seed.is_none() {
+                            self.seed_input.set_value("");
+                        }
+                    }
+                    return;
+                }
+                if self.name_input.editing {
+                    self.name_input.handle_input();
+                    return;
+                }
+
+                let any_clicked = [
+                    &self.start_button,
+                    &self.mode_button,
+                    &self.seed_input.button,
+                    &self.name_input.button,
+                    &self.settings_button,
+                    &self.leaderboard_button,
+                    &self.replay_button,
+                    &self.exit_button,
+                    &self.board_button,
+                    &self.boundary_button,
+                    &self.levels_button,
+                    &self.watch_ai_button,
+                    &self.watch_solver_button,
+                ]
+                .iter()
+                .any(|button| button.is_clicked());
+                if any_clicked {
+                    self.sound_manager.play(SoundEffect::MenuClick);
+                }
+
+                if self.start_button.is_clicked() || self.watch_ai_button.is_clicked() || self.watch_solver_button.is_clicked() {
+                    self.controller = if self.watch_ai_button.is_clicked() {
+                        Box::new(Autopilot::new())
+                    } else if self.watch_solver_button.is_clicked() {
+                        Box::new(HamiltonianSolver::new())
                     } else {
-                        GameMode::Normal
+                        human_controller()
                     };
-                    self.mode_button.text = if self.game_mode == GameMode::Normal {
-                        "Mode: Normal".to_string()
+                    if self.game_mode == GameMode::Campaign {
+                        self.start_campaign();
                     } else {
-                        "Mode: Obstacle".to_string()
+                        self.selected_level = None;
+                        self.reset_game();
+                        self.state = GameState::Playing;
+                    }
+                } else if self.exit_button.is_clicked() {
+                    std::process::exit(0);
+                } else if self.board_button.is_clicked() {
+                    self.settings.board_preset = match self.settings.board_preset {
+                        BoardPreset::Small => BoardPreset::Medium,
+                        BoardPreset::Medium => BoardPreset::Large,
+                        BoardPreset::Large => BoardPreset::Custom,
+                        BoardPreset::Custom => BoardPreset::Small,
                     };
+                    self.board_button.text = board_label(&self.settings.board());
+                    self.save_settings();
+                } else if self.boundary_button.is_clicked() {
+                    let rules = BoundaryRule::ALL;
+                    let current = self.settings.boundary(self.game_mode);
+                    let index = rules.iter().position(|r| *r == current).unwrap_or(0);
+                    let next = rules[(index + 1) % rules.len()];
+                    self.settings.set_boundary(self.game_mode, next);
+                    self.boundary_button.text = boundary_label(next);
+                    self.save_settings();
+                } else if self.settings_button.is_clicked() {
+                    self.state = GameState::Settings;
+                } else if self.mode_button.is_clicked() {
+                    let modes = GameMode::ALL;
+                    let index = modes.iter().position(|m| *m == self.game_mode).unwrap_or(0);
+                    self.game_mode = modes[(index + 1) % modes.len()];
+                    self.mode_button.text = mode_label(self.game_mode);
+                    self.boundary_button.text = boundary_label(self.settings.boundary(self.game_mode));
+                } else if self.leaderboard_button.is_clicked() {
+                    self.leaderboard_mode = self.game_mode;
+                    self.state = GameState::Leaderboard;
+                } else if self.levels_button.is_clicked() {
+                    self.reload_levels();
+                    self.state = GameState::LevelSelect;
+                } else if self.replay_button.is_clicked() {
+                    if let Some(replay) = Self::load_replay(&data_path(LAST_REPLAY_FILE)) {
+                        self.start_replay(replay);
+                    }
+                } else {
+                    self.seed_input.handle_input();
+                    self.name_input.handle_input();
                 }
             },
-            GameState::Playing => {
-                if is_key_pressed(KeyCode::Up) {
-                    self.snake.change_direction(Direction::Up);
-                } else if is_key_pressed(KeyCode::Down) {
-                    self.snake.change_direction(Direction::Down);
+            GameState::Settings => {
+                let mut changed = false;
+                for slider in [&mut self.master_slider, &mut self.music_slider, &mut self.effects_slider] {
+                    changed |= slider.handle_input();
+                }
+                self.settings.master_volume = self.master_slider.value;
+                self.settings.music_volume = self.music_slider.value;
+                self.settings.effects_volume = self.effects_slider.value;
+
+                if self.maze_regen_button.is_clicked() {
+                    let index = MAZE_REGEN_CHOICES
+                        .iter()
+                        .position(|choice| *choice == self.settings.maze_regen_length)
+                        .unwrap_or(0);
+                    self.settings.maze_regen_length = MAZE_REGEN_CHOICES[(index + 1) % MAZE_REGEN_CHOICES.len()];
+                    self.maze_regen_button.text = maze_regen_label(self.settings.maze_regen_length);
+                    changed = true;
+                }
+
+                if self.smooth_button.is_clicked() {
+                    self.settings.smooth_movement = !self.settings.smooth_movement;
+                    self.smooth_button.text = smooth_label(self.settings.smooth_movement);
+                    changed = true;
+                }
+
+                if self.mute_button.is_clicked() {
+                    self.settings.muted = !self.settings.muted;
+                    self.mute_button.text = mute_label(self.settings.muted);
+                    changed = true;
+                }
+                self.apply_settings();
+
+                if changed {
+                    self.sound_manager.play(SoundEffect::MenuClick);
+                    self.save_settings();
+                }
+
+                if self.back_button.is_clicked() || is_key_pressed(KeyCode::Escape) {
+                    self.state = GameState::Menu;
+                }
+            },
+            GameState::Leaderboard => {
+                let modes = GameMode::ALL;
+                let index = modes.iter().position(|m| *m == self.leaderboard_mode).unwrap_or(0);
+                if is_key_pressed(KeyCode::Right) {
+                    self.leaderboard_mode = modes[(index + 1) % modes.len()];
                 } else if is_key_pressed(KeyCode::Left) {
-                    self.snake.change_direction(Direction::Left);
-                } else if is_key_pressed(KeyCode::Right) {
-                    self.snake.change_direction(Direction::Right);
+                    self.leaderboard_mode = modes[(index + modes.len() - 1) % modes.len()];
+                } else if is_key_pressed(KeyCode::Escape) {
+                    self.state = GameState::Menu;
+                }
+            },
+            GameState::LevelSelect => {
+                if is_key_pressed(KeyCode::Down) && self.level_index + 1 < self.levels.len() {
+                    self.level_index += 1;
+                } else if is_key_pressed(KeyCode::Up) && self.level_index > 0 {
+                    self.level_index -= 1;
+                } else if is_key_pressed(KeyCode::Enter) && !self.levels.is_empty() {
+                    let level = Arc::clone(&self.levels[self.level_index]);
+                    if self.is_level_unlocked(&level) {
+                        self.controller = human_controller();
+                        self.start_level(level);
+                    }
                 } else if is_key_pressed(KeyCode::Escape) {
                     self.state = GameState::Menu;
+                }
+            },
+            GameState::Playing => {
+                // ลูกศรอ่านใน KeyboardController::poll ตอน update
+                if is_key_pressed(KeyCode::Escape) {
+                    self.state = GameState::Menu;
                 } else if is_key_pressed(KeyCode::Space) {
+                    self.sound_manager.play(SoundEffect::Pause);
                     self.state = GameState::Paused;
                 }
             },
             GameState::Paused => {
                 if is_key_pressed(KeyCode::Space) {
+                    self.sound_manager.play(SoundEffect::Pause);
                     self.state = GameState::Playing;
                 } else if is_key_pressed(KeyCode::Escape) {
                     self.state = GameState::Menu;
@@ -469,9 +1383,51 @@ impl SnakeGame {
             },
             GameState::GameOver => {
                 if is_key_pressed(KeyCode::Enter) {
-                    self.reset_game();
-                    self.state = GameState::Playing;
+                    let next_level = if self.sim.state == SimulationState::LevelComplete {
+                        self.next_campaign_level()
+                    } else {
+                        None
+                    };
+                    if let Some(level) = next_level {
+                        self.start_level(level);
+                    } else {
+                        self.reset_game();
+                        self.state = GameState::Playing;
+                    }
+                } else if is_key_pressed(KeyCode::R) {
+                    self.start_replay(self.sim.recording.clone());
+                } else if is_key_pressed(KeyCode::Escape) {
+                    self.state = GameState::Menu;
+                }
+            },
+            GameState::Victory => {
+                if self.victory_name_input.editing {
+                    // Enter บันทึกชื่อที่พิมพ์ Esc ใช้ชื่อเดิม
+                    self.victory_name_input.handle_input();
+                    if !self.victory_name_input.editing {
+                        self.save_victory_entry();
+                    }
+                } else if is_key_pressed(KeyCode::Enter) {
+                    if let Some(level) = self.next_campaign_level() {
+                        self.start_level(level);
+                    } else {
+                        self.reset_game();
+                        self.state = GameState::Playing;
+                    }
+                } else if is_key_pressed(KeyCode::R) {
+                    self.start_replay(self.sim.recording.clone());
+                } else if is_key_pressed(KeyCode::Escape) {
+                    self.state = GameState::Menu;
+                }
+            },
+            GameState::Replay => {
+                if is_key_pressed(KeyCode::Space) {
+                    self.replay_paused = !self.replay_paused;
+                } else if is_key_pressed(KeyCode::Period) {
+                    self.replay_paused = true;
+                    self.replay_step_requested = true;
                 } else if is_key_pressed(KeyCode::Escape) {
+                    self.replay_player = None;
                     self.state = GameState::Menu;
                 }
             },
@@ -484,7 +1440,21 @@ async fn main() {
     request_new_screen_size(800.0, 600.0);
     next_frame().await;
 
-    let mut game = SnakeGame::new();
+    // ปิดเสียงได้ด้วย --no-audio, ตั้งโฟลเดอร์เสียงเองได้ด้วย SNAKE_GUI_ASSETS_DIR
+    let (sound_manager, music_manager) = if std::env::args().any(|arg| arg == "--no-audio") {
+        (SoundManager::new(), MusicManager::new())
+    } else {
+        let assets_dir = std::env::var("SNAKE_GUI_ASSETS_DIR").unwrap_or_else(|_| "assets".to_string());
+        (
+            SoundManager::load(&Path::new(&assets_dir).join("sounds")).await,
+            MusicManager::load().await,
+        )
+    };
+
+    let mut game = SnakeGame::new(sound_manager, music_manager);
+    if let Some(replay) = arg_value("--replay").and_then(|path| SnakeGame::load_replay(Path::new(&path))) {
+        game.start_replay(replay);
+    }
 
     loop {
         game.update_button_positions();
@@ -492,7 +1462,13 @@ async fn main() {
 
         if game.state == GameState::Playing {
             game.update();
+        } else if game.state == GameState::Replay {
+            game.update_replay();
+        } else {
+            // ไม่สะสมเวลาตอนหยุดเกม กลับมาเล่นต่อจะได้ไม่กระโดด
+            game.tick_accumulator = 0.0;
         }
+        game.update_music();
 
         game.draw();
         next_frame().await;
@@ -1,3 +1,7 @@
 pub mod button;
+pub mod slider;
+pub mod text_input;
 
-pub use button::Button; 
\ No newline at end of file
+pub use button::Button;
+pub use slider::Slider;
+pub use text_input::TextInput;
\ No newline at end of file
@@ -0,0 +1,71 @@
+use macroquad::prelude::*;
+
+pub struct Slider {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub label: String,
+    pub value: f32,
+    pub dragging: bool,
+}
+
+impl Slider {
+    pub fn new(x: f32, y: f32, width: f32, height: f32, label: &str, value: f32) -> Self {
+        Slider {
+            x,
+            y,
+            width,
+            height,
+            label: label.to_string(),
+            value: value.clamp(0.0, 1.0),
+            dragging: false,
+        }
+    }
+
+    pub fn update_position(&mut self, x: f32, y: f32) {
+        self.x = x;
+        self.y = y;
+    }
+
+    fn is_hovered(&self) -> bool {
+        let mouse_pos = mouse_position();
+        mouse_pos.0 >= self.x && mouse_pos.0 <= self.x + self.width
+            && mouse_pos.1 >= self.y && mouse_pos.1 <= self.y + self.height
+    }
+
+    pub fn draw(&self) {
+        draw_text(
+            &format!("{}: {}%", self.label, (self.value * 100.0).round()),
+            self.x,
+            self.y - 8.0,
+            20.0,
+            WHITE,
+        );
+
+        let track_y = self.y + self.height / 2.0;
+        draw_line(self.x, track_y, self.x + self.width, track_y, 4.0, GRAY);
+        draw_line(self.x, track_y, self.x + self.width * self.value, track_y, 4.0, GREEN);
+
+        let color = if self.dragging || self.is_hovered() { WHITE } else { LIGHTGRAY };
+        draw_circle(self.x + self.width * self.value, track_y, self.height / 2.0, color);
+    }
+
+    // คืนค่า true เมื่อปล่อยเมาส์หลังลาก (ค่าเปลี่ยนเสร็จแล้ว)
+    pub fn handle_input(&mut self) -> bool {
+        if is_mouse_button_pressed(MouseButton::Left) && self.is_hovered() {
+            self.dragging = true;
+        }
+
+        if self.dragging {
+            let mouse_x = mouse_position().0;
+            self.value = ((mouse_x - self.x) / self.width).clamp(0.0, 1.0);
+
+            if !is_mouse_button_down(MouseButton::Left) {
+                self.dragging = false;
+                return true;
+            }
+        }
+        false
+    }
+}
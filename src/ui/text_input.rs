@@ -0,0 +1,94 @@
+use macroquad::prelude::*;
+use crate::ui::button::Button;
+
+pub struct TextInput {
+    pub button: Button,
+    pub label: String,
+    pub value: String,
+    pub placeholder: String,
+    pub max_len: usize,
+    pub editing: bool,
+    pub allowed: fn(char) -> bool,
+    previous: String,
+}
+
+impl TextInput {
+    pub fn new(x: f32, y: f32, width: f32, height: f32, label: &str, placeholder: &str) -> Self {
+        let mut input = TextInput {
+            button: Button::new(x, y, width, height, String::new()),
+            label: label.to_string(),
+            value: String::new(),
+            placeholder: placeholder.to_string(),
+            max_len: 20,
+            editing: false,
+            allowed: |c| c.is_ascii_graphic(),
+            previous: String::new(),
+        };
+        input.refresh_text();
+        input
+    }
+
+    pub fn update_position(&mut self, x: f32, y: f32) {
+        self.button.update_position(x, y);
+    }
+
+    pub fn set_value(&mut self, value: &str) {
+        self.value = value.to_string();
+        self.refresh_text();
+    }
+
+    // เริ่มพิมพ์โดยไม่ต้องรอให้คลิก
+    pub fn start_editing(&mut self) {
+        self.editing = true;
+        self.previous = self.value.clone();
+        // ทิ้งตัวอักษรที่ค้างอยู่ในคิวก่อนเริ่มพิมพ์
+        while get_char_pressed().is_some() {}
+        self.refresh_text();
+    }
+
+    fn refresh_text(&mut self) {
+        let shown = if self.editing {
+            format!("{}_", self.value)
+        } else if self.value.is_empty() {
+            self.placeholder.clone()
+        } else {
+            self.value.clone()
+        };
+        self.button.text = format!("{}: {}", self.label, shown);
+    }
+
+    pub fn draw(&self) {
+        self.button.draw();
+    }
+
+    // คืนค่า true เมื่อผู้ใช้กด Enter เพื่อยืนยันค่า
+    pub fn handle_input(&mut self) -> bool {
+        if !self.editing {
+            if self.button.is_clicked() {
+                self.start_editing();
+            }
+            return false;
+        }
+
+        let mut committed = false;
+        while let Some(c) = get_char_pressed() {
+            if (self.allowed)(c) && self.value.len() < self.max_len {
+                self.value.push(c);
+            }
+        }
+
+        if is_key_pressed(KeyCode::Backspace) {
+            self.value.pop();
+        }
+        if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::KpEnter) {
+            self.editing = false;
+            committed = true;
+        } else if is_key_pressed(KeyCode::Escape) {
+            self.editing = false;
+            self.value = std::mem::take(&mut self.previous);
+        }
+
+        self.refresh_text();
+        committed
+    }
+}
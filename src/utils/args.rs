@@ -0,0 +1,56 @@
+use std::str::FromStr;
+
+// ค่าที่ตามหลัง flag ในบรรทัดคำสั่ง เช่น --seed 42
+pub fn arg_value(name: &str) -> Option<String> {
+    let args: Vec<String> = std::env::args().collect();
+    args.iter()
+        .position(|arg| arg == name)
+        .and_then(|i| args.get(i + 1))
+        .cloned()
+}
+
+// แปลงค่าของ flag ใช้ default ถ้าไม่ได้ใส่มา
+pub fn parse_arg<T: FromStr>(name: &str, default: T) -> Result<T, String> {
+    match arg_value(name) {
+        Some(value) => value.parse().map_err(|_| format!("invalid value for {}: '{}'", name, value)),
+        None => Ok(default),
+    }
+}
+
+// ทุก argument ต้องเป็น flag ที่รู้จักตามด้วยค่า พิมพ์ผิด (เช่น --game 50) จะได้ไม่รันด้วยค่า default เงียบ ๆ
+pub fn check_flags(flags: &[&str]) -> Result<(), String> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    check_flag_list(&args, flags)
+}
+
+fn check_flag_list(args: &[String], flags: &[&str]) -> Result<(), String> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if !flags.contains(&arg.as_str()) {
+            return Err(format!("unknown argument '{}' (expected {})", arg, flags.join(", ")));
+        }
+        if args.next().is_none() {
+            return Err(format!("missing value for {}", arg));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(args: &[&str]) -> Result<(), String> {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        check_flag_list(&args, &["--games", "--seed"])
+    }
+
+    #[test]
+    fn accepts_only_known_flags_with_values() {
+        assert_eq!(check(&[]), Ok(()));
+        assert_eq!(check(&["--games", "5", "--seed", "--games"]), Ok(()));
+        assert_eq!(check(&["--game", "50"]), Err("unknown argument '--game' (expected --games, --seed)".to_string()));
+        assert_eq!(check(&["--seed", "1", "extra"]), Err("unknown argument 'extra' (expected --games, --seed)".to_string()));
+        assert_eq!(check(&["--games", "5", "--seed"]), Err("missing value for --seed".to_string()));
+    }
+}
@@ -0,0 +1,92 @@
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use crate::game::Level;
+use crate::utils::file_manager::{data_path, read_with_backup, write_atomic};
+
+const CAMPAIGN_FILE: &str = "campaign.txt";
+const CAMPAIGN_HEADER: &str = "snake-campaign 1";
+
+// ด่าน Campaign คือด่านที่มี goal เรียงตามชื่อไฟล์
+pub fn campaign_levels(levels: &[Arc<Level>]) -> Vec<Arc<Level>> {
+    levels.iter().filter(|level| level.is_campaign()).cloned().collect()
+}
+
+// เก็บเฉพาะ id ของด่านที่ผ่านแล้ว ด่านถัดไปจะปลดล็อกเมื่อด่านก่อนหน้าผ่าน
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CampaignProgress {
+    pub completed: Vec<String>,
+}
+
+impl CampaignProgress {
+    pub fn is_completed(&self, level_id: &str) -> bool {
+        self.completed.iter().any(|id| id == level_id)
+    }
+
+    // คืนค่า true ถ้าเพิ่งผ่านด่านนี้เป็นครั้งแรก
+    pub fn complete(&mut self, level_id: &str) -> bool {
+        if self.is_completed(level_id) {
+            return false;
+        }
+        self.completed.push(level_id.to_string());
+        true
+    }
+
+    pub fn is_unlocked(&self, campaign: &[Arc<Level>], index: usize) -> bool {
+        index == 0 || campaign.get(index - 1).is_some_and(|previous| self.is_completed(&previous.id))
+    }
+
+    // ด่านแรกที่ยังไม่ผ่าน (ผ่านหมดแล้วจะได้ด่านสุดท้าย)
+    pub fn next_index(&self, campaign: &[Arc<Level>]) -> usize {
+        campaign
+            .iter()
+            .position(|level| !self.is_completed(&level.id))
+            .unwrap_or(campaign.len().saturating_sub(1))
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{}\n", CAMPAIGN_HEADER);
+        for id in &self.completed {
+            text.push_str(&format!("completed {}\n", id));
+        }
+        text
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines().enumerate();
+        match lines.next() {
+            Some((_, line)) if line.trim() == CAMPAIGN_HEADER => {}
+            _ => return Err("not a campaign progress file".to_string()),
+        }
+
+        let mut progress = CampaignProgress::default();
+        for (index, line) in lines {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            match line.split_once(' ') {
+                Some(("completed", id)) => {
+                    progress.complete(id.trim());
+                }
+                _ => return Err(format!("line {}: expected 'completed <level>'", index + 1)),
+            }
+        }
+        Ok(progress)
+    }
+
+    pub fn path() -> PathBuf {
+        data_path(CAMPAIGN_FILE)
+    }
+
+    pub fn load() -> io::Result<Self> {
+        match read_with_backup(&Self::path(), Self::from_text) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(CampaignProgress::default()),
+            result => result,
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        write_atomic(&Self::path(), &self.to_text())
+    }
+}
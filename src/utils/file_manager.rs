@@ -1,20 +1,224 @@
-use std::fs::{File, OpenOptions};
-use std::io::{Read, Write};
-
-pub fn load_high_score() -> usize {
-    if let Ok(mut file) = File::open("highscore.txt") {
-        let mut contents = String::new();
-        if file.read_to_string(&mut contents).is_ok() {
-            if let Ok(score) = contents.trim().parse::<usize>() {
-                return score;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use crate::game::level::{load_levels, Level, LevelLoadError};
+use crate::utils::leaderboard::Leaderboard;
+
+const APP_DIR_NAME: &str = "snake_gui";
+const DATA_DIR_ENV: &str = "SNAKE_GUI_DATA_DIR";
+const LEGACY_HIGH_SCORE_FILE: &str = "highscore.txt";
+const LEADERBOARD_FILE: &str = "leaderboard.txt";
+const LEVELS_DIR: &str = "levels";
+
+// โฟลเดอร์เก็บข้อมูลของผู้ใช้ (ตั้งเองได้ผ่าน SNAKE_GUI_DATA_DIR)
+pub fn data_dir() -> PathBuf {
+    if let Some(dir) = env::var_os(DATA_DIR_ENV).filter(|d| !d.is_empty()) {
+        return PathBuf::from(dir);
+    }
+    platform_data_dir()
+        .map(|dir| dir.join(APP_DIR_NAME))
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+#[cfg(target_os = "windows")]
+fn platform_data_dir() -> Option<PathBuf> {
+    env::var_os("APPDATA").map(PathBuf::from)
+}
+
+#[cfg(target_os = "macos")]
+fn platform_data_dir() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn platform_data_dir() -> Option<PathBuf> {
+    env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+}
+
+pub fn data_path(name: &str) -> PathBuf {
+    data_dir().join(name)
+}
+
+fn sibling_path(path: &Path, extension: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(extension);
+    path.with_file_name(name)
+}
+
+pub fn backup_path(path: &Path) -> PathBuf {
+    sibling_path(path, ".bak")
+}
+
+// เขียนลงไฟล์ชั่วคราวก่อนแล้วค่อย rename ทับ ไฟล์เดิมจึงไม่เสียถ้าโปรแกรมพังกลางทาง
+// และเก็บสำเนาไฟล์ก่อนหน้าไว้เป็น .bak
+pub fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+
+    // พังขั้นไหนก็ตามต้องลบไฟล์ชั่วคราวทิ้ง ไม่ให้เหลือไฟล์ที่เขียนไม่ครบค้างอยู่
+    let temp_path = sibling_path(path, ".tmp");
+    let result = write_temp(&temp_path, contents)
+        .and_then(|_| if path.exists() { fs::copy(path, backup_path(path)).map(|_| ()) } else { Ok(()) })
+        .and_then(|_| fs::rename(&temp_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+fn write_temp(temp_path: &Path, contents: &str) -> io::Result<()> {
+    let mut file = File::create(temp_path)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()
+}
+
+// อ่านไฟล์แล้วแปลงด้วย parse ถ้าไฟล์หลักเสียให้ลองอ่านจาก .bak แทน
+pub fn read_with_backup<T>(path: &Path, parse: impl Fn(&str) -> Result<T, String>) -> io::Result<T> {
+    let primary = fs::read_to_string(path).and_then(|contents| {
+        parse(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    });
+
+    match primary {
+        Ok(value) => Ok(value),
+        Err(e) => {
+            let backup = fs::read_to_string(backup_path(path))
+                .ok()
+                .and_then(|contents| parse(&contents).ok());
+            match backup {
+                Some(value) => {
+                    eprintln!("{} is unreadable ({}), using backup", path.display(), e);
+                    Ok(value)
+                }
+                None => Err(e),
+            }
+        }
+    }
+}
+
+pub fn leaderboard_path() -> PathBuf {
+    data_path(LEADERBOARD_FILE)
+}
+
+// โหลดตารางคะแนน ถ้ายังไม่มีไฟล์ใหม่ให้ย้ายข้อมูลจาก highscore.txt แบบเก่า
+pub fn load_leaderboard() -> io::Result<Leaderboard> {
+    let path = leaderboard_path();
+    match read_with_backup(&path, Leaderboard::from_text) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        result => return result,
+    }
+
+    let legacy_paths = [data_path(LEGACY_HIGH_SCORE_FILE), PathBuf::from(LEGACY_HIGH_SCORE_FILE)];
+    for legacy_path in &legacy_paths {
+        if let Ok(contents) = fs::read_to_string(legacy_path) {
+            if let Ok(leaderboard) = Leaderboard::from_text(&contents) {
+                save_leaderboard(&leaderboard)?;
+                return Ok(leaderboard);
             }
         }
     }
-    0
+    Ok(Leaderboard::default())
+}
+
+pub fn save_leaderboard(leaderboard: &Leaderboard) -> io::Result<()> {
+    write_atomic(&leaderboard_path(), &leaderboard.to_text())
 }
 
-pub fn save_high_score(score: usize) {
-    if let Ok(mut file) = OpenOptions::new().write(true).create(true).truncate(true).open("highscore.txt") {
-        let _ = write!(file, "{}", score);
+// ด่านที่มากับเกม (levels/ ข้างตัวเกม) ตามด้วยด่านของผู้ใช้ใน data_dir/levels
+pub fn level_dirs() -> Vec<PathBuf> {
+    vec![PathBuf::from(LEVELS_DIR), data_path(LEVELS_DIR)]
+}
+
+// ด่านของผู้ใช้ที่ชื่อไฟล์ซ้ำกับด่านที่มากับเกมจะใช้แทนของเดิม
+pub fn load_all_levels() -> (Vec<Level>, Vec<LevelLoadError>) {
+    let mut levels: Vec<Level> = Vec::new();
+    let mut errors = Vec::new();
+    for dir in level_dirs() {
+        let (loaded, failed) = load_levels(&dir);
+        for level in loaded {
+            match levels.iter_mut().find(|existing| existing.id == level.id) {
+                Some(existing) => *existing = level,
+                None => levels.push(level),
+            }
+        }
+        errors.extend(failed);
+    }
+    (levels, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // โฟลเดอร์ชั่วคราวแยกตาม test ลบของเก่าทิ้งก่อนทุกครั้ง
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("snake_gui_test_{}_{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        dir
     }
-} 
\ No newline at end of file
+
+    fn parse_number(text: &str) -> Result<u32, String> {
+        text.trim().parse().map_err(|_| format!("not a number: '{}'", text))
+    }
+
+    fn file_names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn writes_and_reads_back() {
+        let dir = temp_dir("write");
+        let path = dir.join("nested").join("score.txt");
+        write_atomic(&path, "12\n").unwrap();
+        assert_eq!(read_with_backup(&path, parse_number).unwrap(), 12);
+        assert!(!backup_path(&path).exists());
+
+        // เขียนทับแล้วไฟล์ก่อนหน้ากลายเป็น .bak
+        write_atomic(&path, "34\n").unwrap();
+        assert_eq!(read_with_backup(&path, parse_number).unwrap(), 34);
+        assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), "12\n");
+        assert_eq!(file_names(path.parent().unwrap()), ["score.txt", "score.txt.bak"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn falls_back_to_backup() {
+        let dir = temp_dir("backup");
+        let path = dir.join("score.txt");
+        write_atomic(&path, "5").unwrap();
+        write_atomic(&path, "6").unwrap();
+
+        fs::write(&path, "garbage").unwrap();
+        assert_eq!(read_with_backup(&path, parse_number).unwrap(), 5);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(read_with_backup(&path, parse_number).unwrap(), 5);
+
+        // ไฟล์หลักหายและ .bak เสีย ได้ error ของไฟล์หลัก
+        fs::write(backup_path(&path), "garbage").unwrap();
+        assert_eq!(read_with_backup(&path, parse_number).unwrap_err().kind(), io::ErrorKind::NotFound);
+        fs::write(&path, "also garbage").unwrap();
+        assert_eq!(read_with_backup(&path, parse_number).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // เขียนไม่สำเร็จ (ปลายทางเป็นโฟลเดอร์) ต้องไม่เหลือไฟล์ชั่วคราวค้าง
+    #[test]
+    fn failed_write_leaves_no_partial_file() {
+        let dir = temp_dir("partial");
+        let path = dir.join("score.txt");
+        fs::create_dir_all(&path).unwrap();
+        assert!(write_atomic(&path, "7").is_err());
+        assert_eq!(file_names(&dir), ["score.txt"]);
+        assert!(path.is_dir());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,279 @@
+use crate::game::GameMode;
+
+const LEADERBOARD_HEADER: &str = "snake-leaderboard";
+const LEADERBOARD_VERSION: u32 = 1;
+pub const LEADERBOARD_SIZE: usize = 10;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    pub score: usize,
+    pub length: usize,
+    pub ticks: u64,
+    pub timestamp: u64,
+    pub seed: u64,
+    pub power_ups_collected: u32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Leaderboard {
+    pub normal: Vec<LeaderboardEntry>,
+    pub obstacle: Vec<LeaderboardEntry>,
+    pub campaign: Vec<LeaderboardEntry>,
+    pub maze: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    pub fn entries(&self, mode: GameMode) -> &[LeaderboardEntry] {
+        match mode {
+            GameMode::Normal => &self.normal,
+            GameMode::Obstacle => &self.obstacle,
+            GameMode::Campaign => &self.campaign,
+            GameMode::Maze => &self.maze,
+        }
+    }
+
+    fn entries_mut(&mut self, mode: GameMode) -> &mut Vec<LeaderboardEntry> {
+        match mode {
+            GameMode::Normal => &mut self.normal,
+            GameMode::Obstacle => &mut self.obstacle,
+            GameMode::Campaign => &mut self.campaign,
+            GameMode::Maze => &mut self.maze,
+        }
+    }
+
+    pub fn best_score(&self, mode: GameMode) -> usize {
+        self.entries(mode).first().map_or(0, |entry| entry.score)
+    }
+
+    pub fn qualifies(&self, mode: GameMode, score: usize) -> bool {
+        let entries = self.entries(mode);
+        score > 0 && (entries.len() < LEADERBOARD_SIZE || entries.iter().any(|e| score > e.score))
+    }
+
+    // คืนค่าอันดับ (เริ่มที่ 0) ถ้าติดตาราง
+    pub fn insert(&mut self, mode: GameMode, entry: LeaderboardEntry) -> Option<usize> {
+        if !self.qualifies(mode, entry.score) {
+            return None;
+        }
+        let entries = self.entries_mut(mode);
+        // คะแนนเท่ากันให้คนที่ทำได้ก่อนอยู่อันดับสูงกว่า
+        let rank = entries.iter().position(|e| entry.score > e.score).unwrap_or(entries.len());
+        entries.insert(rank, entry);
+        entries.truncate(LEADERBOARD_SIZE);
+        Some(rank)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{} {}\n", LEADERBOARD_HEADER, LEADERBOARD_VERSION);
+        for mode in GameMode::ALL {
+            for e in self.entries(mode) {
+                text.push_str(&format!(
+                    "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+                    mode.name(),
+                    sanitize_name(&e.name),
+                    e.score,
+                    e.length,
+                    e.ticks,
+                    e.timestamp,
+                    e.seed,
+                    e.power_ups_collected,
+                ));
+            }
+        }
+        text
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines().enumerate();
+        let header = lines.next().map(|(_, line)| line.trim()).unwrap_or("");
+
+        // ไฟล์ highscore.txt แบบเก่าเก็บแค่ตัวเลขตัวเดียว
+        if let Ok(score) = header.parse::<usize>() {
+            return Ok(Self::from_legacy_score(score));
+        }
+
+        let version = header
+            .strip_prefix(LEADERBOARD_HEADER)
+            .and_then(|v| v.trim().parse::<u32>().ok())
+            .ok_or("not a leaderboard file")?;
+        if version > LEADERBOARD_VERSION {
+            return Err(format!("unsupported leaderboard version {}", version));
+        }
+
+        let mut leaderboard = Leaderboard::default();
+        for (index, line) in lines {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let line_number = index + 1;
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() != 8 {
+                return Err(format!("line {}: expected 8 fields, found {}", line_number, fields.len()));
+            }
+            let mode = GameMode::from_name(fields[0])
+                .ok_or_else(|| format!("line {}: unknown mode '{}'", line_number, fields[0]))?;
+            let number = |i: usize| -> Result<u64, String> {
+                fields[i].parse().map_err(|_| format!("line {}: invalid number '{}'", line_number, fields[i]))
+            };
+            let entry = LeaderboardEntry {
+                name: fields[1].to_string(),
+                score: number(2)? as usize,
+                length: number(3)? as usize,
+                ticks: number(4)?,
+                timestamp: number(5)?,
+                seed: number(6)?,
+                power_ups_collected: number(7)? as u32,
+            };
+            leaderboard.entries_mut(mode).push(entry);
+        }
+
+        for mode in GameMode::ALL {
+            let entries = leaderboard.entries_mut(mode);
+            entries.sort_by_key(|e| std::cmp::Reverse(e.score));
+            entries.truncate(LEADERBOARD_SIZE);
+        }
+        Ok(leaderboard)
+    }
+
+    fn from_legacy_score(score: usize) -> Self {
+        let mut leaderboard = Leaderboard::default();
+        if score > 0 {
+            leaderboard.normal.push(LeaderboardEntry {
+                name: "Player".to_string(),
+                score,
+                length: score + 1,
+                ticks: 0,
+                timestamp: 0,
+                seed: 0,
+                power_ups_collected: 0,
+            });
+        }
+        leaderboard
+    }
+}
+
+fn sanitize_name(name: &str) -> String {
+    let name: String = name.chars().filter(|c| !c.is_control()).collect();
+    if name.trim().is_empty() {
+        "Player".to_string()
+    } else {
+        name
+    }
+}
+
+pub fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+// แปลง unix timestamp เป็น YYYY-MM-DD (UTC) โดยไม่ต้องพึ่ง crate ภายนอก
+pub fn format_date(timestamp: u64) -> String {
+    if timestamp == 0 {
+        return "-".to_string();
+    }
+    let days = (timestamp / 86_400) as i64;
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, score: usize) -> LeaderboardEntry {
+        LeaderboardEntry {
+            name: name.to_string(),
+            score,
+            length: score + 1,
+            ticks: 600,
+            timestamp: 1_700_000_000,
+            seed: 9,
+            power_ups_collected: 2,
+        }
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.insert(GameMode::Normal, entry("Ann", 12));
+        leaderboard.insert(GameMode::Normal, entry("Bo", 30));
+        leaderboard.insert(GameMode::Maze, entry("Cy", 4));
+        let parsed = Leaderboard::from_text(&leaderboard.to_text()).unwrap();
+        assert_eq!(parsed.normal, vec![entry("Bo", 30), entry("Ann", 12)]);
+        assert_eq!(parsed.maze, vec![entry("Cy", 4)]);
+        assert!(parsed.obstacle.is_empty() && parsed.campaign.is_empty());
+    }
+
+    #[test]
+    fn sorts_and_truncates_on_load() {
+        let mut text = format!("{} {}\n", LEADERBOARD_HEADER, LEADERBOARD_VERSION);
+        for score in 1..=LEADERBOARD_SIZE + 2 {
+            text.push_str(&format!("normal\tP{}\t{}\t2\t0\t0\t0\t0\n\n", score, score));
+        }
+        let parsed = Leaderboard::from_text(&text).unwrap();
+        assert_eq!(parsed.normal.len(), LEADERBOARD_SIZE);
+        assert_eq!(parsed.best_score(GameMode::Normal), LEADERBOARD_SIZE + 2);
+        assert_eq!(parsed.normal.last().unwrap().score, 3);
+    }
+
+    #[test]
+    fn migrates_legacy_high_score() {
+        let parsed = Leaderboard::from_text("42\n").unwrap();
+        assert_eq!(parsed.normal.len(), 1);
+        assert_eq!(parsed.normal[0].name, "Player");
+        assert_eq!(parsed.best_score(GameMode::Normal), 42);
+        assert!(parsed.obstacle.is_empty());
+        assert!(Leaderboard::from_text("0").unwrap().normal.is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_files() {
+        let header = format!("{} {}\n", LEADERBOARD_HEADER, LEADERBOARD_VERSION);
+        let cases = [
+            ("".to_string(), "not a leaderboard file"),
+            ("high scores\n".to_string(), "not a leaderboard file"),
+            (format!("{} 2\n", LEADERBOARD_HEADER), "unsupported leaderboard version 2"),
+            (format!("{}normal\tAnn\t3\n", header), "line 2: expected 8 fields, found 3"),
+            (format!("{}arcade\tAnn\t3\t4\t0\t0\t0\t0\n", header), "line 2: unknown mode 'arcade'"),
+            (format!("{}normal\tAnn\tlots\t4\t0\t0\t0\t0\n", header), "line 2: invalid number 'lots'"),
+        ];
+        for (text, message) in cases {
+            assert_eq!(Leaderboard::from_text(&text).unwrap_err(), message, "{}", text);
+        }
+    }
+
+    #[test]
+    fn ties_rank_below_earlier_runs() {
+        let mut leaderboard = Leaderboard::default();
+        assert_eq!(leaderboard.insert(GameMode::Normal, entry("First", 10)), Some(0));
+        assert_eq!(leaderboard.insert(GameMode::Normal, entry("Second", 10)), Some(1));
+        assert_eq!(leaderboard.insert(GameMode::Normal, entry("Zero", 0)), None);
+    }
+
+    #[test]
+    fn strips_tabs_from_names() {
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.insert(GameMode::Normal, entry("A\tB", 5));
+        leaderboard.insert(GameMode::Normal, entry(" ", 3));
+        let parsed = Leaderboard::from_text(&leaderboard.to_text()).unwrap();
+        assert_eq!(parsed.normal[0].name, "AB");
+        assert_eq!(parsed.normal[1].name, "Player");
+    }
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(format_date(0), "-");
+        assert_eq!(format_date(1_700_000_000), "2023-11-14");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+    }
+}
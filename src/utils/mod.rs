@@ -1,5 +1,13 @@
+pub mod args;
+pub mod campaign;
 pub mod constants;
 pub mod file_manager;
+pub mod leaderboard;
+pub mod settings;
 
+pub use args::{arg_value, check_flags, parse_arg};
+pub use campaign::{campaign_levels, CampaignProgress};
 pub use constants::*;
-pub use file_manager::*; 
\ No newline at end of file
+pub use file_manager::*;
+pub use leaderboard::{Leaderboard, LeaderboardEntry};
+pub use settings::Settings;
\ No newline at end of file
@@ -0,0 +1,155 @@
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+use crate::audio::VolumeMixer;
+use crate::game::{Board, BoardPreset, BoundaryRule, GameMode};
+use crate::utils::file_manager::{data_path, read_with_backup, write_atomic};
+
+const SETTINGS_FILE: &str = "settings.txt";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settings {
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub effects_volume: f32,
+    pub muted: bool,
+    pub board_preset: BoardPreset,
+    // ใช้เมื่อ board_preset = custom (ตั้งได้จากไฟล์ settings เท่านั้น)
+    pub custom_board: Board,
+    // กติกาขอบกระดานแยกตามโหมด โหมดที่ไม่อยู่ในนี้ใช้ wrap
+    pub boundaries: Vec<(GameMode, BoundaryRule)>,
+    // โหมด Maze: สร้างเขาวงกตใหม่ทุก ๆ N ช่องที่งูยาวขึ้น (None = ปิด, ในไฟล์เขียนเป็น 0)
+    pub maze_regen_length: Option<usize>,
+    // วาดงูเลื่อนนุ่มระหว่างช่องแทนการกระโดดทีละช่อง
+    pub smooth_movement: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            master_volume: 1.0,
+            music_volume: 0.7,
+            effects_volume: 1.0,
+            muted: false,
+            board_preset: BoardPreset::Medium,
+            custom_board: Board::default(),
+            boundaries: Vec::new(),
+            maze_regen_length: None,
+            smooth_movement: true,
+        }
+    }
+}
+
+fn parse_value<T: FromStr>(value: &str, line_number: usize) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("line {}: invalid value '{}'", line_number, value))
+}
+
+impl Settings {
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![
+            format!("master_volume = {}", self.master_volume),
+            format!("music_volume = {}", self.music_volume),
+            format!("effects_volume = {}", self.effects_volume),
+            format!("muted = {}", self.muted),
+            format!("board_size = {}", self.board_preset.name()),
+            format!("board_width = {}", self.custom_board.width),
+            format!("board_height = {}", self.custom_board.height),
+            format!("maze_regen_length = {}", self.maze_regen_length.unwrap_or(0)),
+            format!("smooth_movement = {}", self.smooth_movement),
+        ];
+        for (mode, rule) in &self.boundaries {
+            lines.push(format!("boundary_{} = {}", mode.name(), rule.name()));
+        }
+        lines.join("\n") + "\n"
+    }
+
+    // ไฟล์แบบ key = value ต่อบรรทัด คีย์ที่ไม่รู้จักจะถูกข้ามไป
+    pub fn from_text(text: &str) -> Result<Self, String> {
+        let mut settings = Settings::default();
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {}: expected key = value", line_number))?;
+            let (key, value) = (key.trim(), value.trim());
+            let volume = || parse_value::<f32>(value, line_number).map(|v| v.clamp(0.0, 1.0));
+            match key {
+                "master_volume" => settings.master_volume = volume()?,
+                "music_volume" => settings.music_volume = volume()?,
+                "effects_volume" => settings.effects_volume = volume()?,
+                "muted" => settings.muted = parse_value(value, line_number)?,
+                "smooth_movement" => settings.smooth_movement = parse_value(value, line_number)?,
+                "board_size" => {
+                    settings.board_preset = BoardPreset::from_name(value)
+                        .ok_or_else(|| format!("line {}: unknown board size '{}'", line_number, value))?;
+                }
+                "board_width" => {
+                    let width = parse_value(value, line_number)?;
+                    settings.custom_board = Board::new(width, settings.custom_board.height);
+                }
+                "board_height" => {
+                    let height = parse_value(value, line_number)?;
+                    settings.custom_board = Board::new(settings.custom_board.width, height);
+                }
+                "maze_regen_length" => {
+                    let length: usize = parse_value(value, line_number)?;
+                    settings.maze_regen_length = Some(length).filter(|length| *length > 0);
+                }
+                _ => {
+                    if let Some(mode) = key.strip_prefix("boundary_").and_then(GameMode::from_name) {
+                        let rule = BoundaryRule::from_name(value)
+                            .ok_or_else(|| format!("line {}: unknown boundary '{}'", line_number, value))?;
+                        settings.set_boundary(mode, rule);
+                    }
+                }
+            }
+        }
+        Ok(settings)
+    }
+
+    pub fn boundary(&self, mode: GameMode) -> BoundaryRule {
+        self.boundaries
+            .iter()
+            .find(|(m, _)| *m == mode)
+            .map_or(BoundaryRule::Wrap, |(_, rule)| *rule)
+    }
+
+    pub fn set_boundary(&mut self, mode: GameMode, rule: BoundaryRule) {
+        self.boundaries.retain(|(m, _)| *m != mode);
+        self.boundaries.push((mode, rule));
+    }
+
+    pub fn board(&self) -> Board {
+        Board::from_preset(self.board_preset, self.custom_board)
+    }
+
+    pub fn mixer(&self) -> VolumeMixer {
+        VolumeMixer {
+            master: self.master_volume,
+            music: self.music_volume,
+            effects: self.effects_volume,
+            muted: self.muted,
+        }
+    }
+
+    pub fn path() -> PathBuf {
+        data_path(SETTINGS_FILE)
+    }
+
+    pub fn load() -> io::Result<Self> {
+        match read_with_backup(&Self::path(), Self::from_text) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Settings::default()),
+            result => result,
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        write_atomic(&Self::path(), &self.to_text())
+    }
+}